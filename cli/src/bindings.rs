@@ -0,0 +1,181 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use ts_rs::{Directory, ExportReport, ExportSink};
+
+use crate::Result;
+
+/// The manifest ts-rs keeps within the output directory, listing the files written since it was
+/// last pruned.
+pub const MANIFEST: &str = ".ts-rs-manifest.json";
+
+/// The bindings within a directory, keyed by their path relative to it, together with its manifest.
+#[derive(Default)]
+pub struct Bindings {
+    pub files: BTreeMap<PathBuf, String>,
+    manifest: Option<String>,
+}
+
+impl Bindings {
+    /// Reads all files within `dir`. If `dir` does not exist, there are none.
+    pub fn read(dir: &Path) -> Result<Self> {
+        let mut bindings = Self::default();
+        bindings.read_dir(dir, Path::new(""))?;
+        Ok(bindings)
+    }
+
+    fn read_dir(&mut self, root: &Path, relative: &Path) -> Result<()> {
+        let entries = match fs::read_dir(root.join(relative)) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        for entry in entries {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                self.read_dir(root, &path)?;
+            } else if path == Path::new(MANIFEST) {
+                self.manifest = Some(fs::read_to_string(entry.path())?);
+            } else {
+                match fs::read_to_string(entry.path()) {
+                    Ok(contents) => {
+                        self.files.insert(path, contents);
+                    }
+                    // bindings are always UTF-8
+                    Err(err) if err.kind() == ErrorKind::InvalidData => (),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Only keeps files which were generated by ts-rs, which start with the header configured by
+    /// `config`.
+    pub fn generated(mut self, config: &ts_rs::Config) -> Self {
+        self.files
+            .retain(|_, contents| config.is_generated(contents));
+        self
+    }
+
+    /// Writes the bindings into `out_dir`. Files which stayed the same are never touched, so
+    /// tools watching `out_dir` only see files which changed.
+    ///
+    /// If `prune` is set, files generated by ts-rs which are no longer exported are removed
+    /// afterwards, and returned together with the report.
+    pub fn write_to(self, out_dir: &Path, prune: bool) -> Result<(ExportReport, Vec<PathBuf>)> {
+        // the manifest lists the files relative to the directory, so it stays valid when copied
+        let target = out_dir.join(MANIFEST);
+        match &self.manifest {
+            Some(manifest) if fs::read_to_string(&target).ok().as_ref() != Some(manifest) => {
+                fs::create_dir_all(out_dir)?;
+                fs::write(&target, manifest)?;
+            }
+            Some(_) => (),
+            None => match fs::remove_file(&target) {
+                Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                _ => (),
+            },
+        }
+
+        let report = self.write_into(&mut Directory::new(out_dir))?;
+        let pruned = match prune {
+            true => ts_rs::prune(out_dir)?
+                .into_iter()
+                .map(|path| match path.strip_prefix(out_dir) {
+                    Ok(relative) => relative.to_owned(),
+                    Err(_) => path,
+                })
+                .collect(),
+            false => vec![],
+        };
+        Ok((report, pruned))
+    }
+
+    /// Writes the bindings into `sink`, reporting which files were created, updated or left as
+    /// they were.
+    pub fn write_into(self, sink: &mut impl ExportSink) -> Result<ExportReport> {
+        let mut report = ExportReport::default();
+        for (path, contents) in self.files {
+            report.write(sink, &path, contents)?;
+        }
+        Ok(report)
+    }
+}
+
+/// Prints the files which were created, updated or removed, relative to the current directory.
+pub fn print(report: &ExportReport, removed: &[PathBuf], out_dir: &Path) {
+    for path in report.changed() {
+        let status = report.files[path].status;
+        println!("{:>12} {}", status, out_dir.join(path).display());
+    }
+    for path in removed {
+        println!("{:>12} {}", "removed", out_dir.join(path).display());
+    }
+    let unchanged = report.files.len() - report.changed().count();
+    println!("{:>12} {} file(s)", "unchanged", unchanged);
+}
+
+#[cfg(test)]
+mod tests {
+    use ts_rs::FileStatus;
+
+    use super::*;
+
+    const NOTE: &str = "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n";
+
+    fn bindings(files: &[(&str, &str)]) -> Bindings {
+        Bindings {
+            files: (files.iter())
+                .map(|(path, contents)| (PathBuf::from(path), format!("{NOTE}{contents}")))
+                .collect(),
+            manifest: None,
+        }
+    }
+
+    #[test]
+    fn write_to() {
+        let dir = std::env::temp_dir().join(format!("cargo-ts-rs-write-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        let previous = bindings(&[("A.ts", "a"), ("B.ts", "b"), ("C.ts", "c")]);
+        previous.write_to(&dir, false).unwrap();
+        let modified = fs::metadata(dir.join("A.ts")).unwrap().modified().unwrap();
+
+        let mut exported = bindings(&[("A.ts", "a"), ("B.ts", "b2"), ("api/D.ts", "d")]);
+        exported.manifest = Some("{\n  \"files\": {\n    \"A.ts\": []\n  }\n}\n".to_owned());
+        let (report, removed) = exported.write_to(&dir, true).unwrap();
+        let written = Bindings::read(&dir).unwrap();
+        let unchanged = fs::metadata(dir.join("A.ts")).unwrap().modified().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let status = |path: &str| report.files[Path::new(path)].status;
+        assert_eq!(status("A.ts"), FileStatus::Unchanged);
+        assert_eq!(status("B.ts"), FileStatus::Updated);
+        assert_eq!(status("api/D.ts"), FileStatus::Created);
+        assert_eq!(removed, [PathBuf::from("C.ts")]);
+        assert_eq!(modified, unchanged);
+
+        let paths = written.files.keys().collect::<Vec<_>>();
+        assert_eq!(paths, ["A.ts", "B.ts", "api/D.ts"]);
+    }
+
+    #[test]
+    fn generated() {
+        let mut files = bindings(&[("A.ts", "")]);
+        files
+            .files
+            .insert("handwritten.ts".into(), "export const a = 1;\n".into());
+
+        let generated = files.generated(&ts_rs::Config::default());
+        assert_eq!(
+            generated.files.keys().collect::<Vec<_>>(),
+            [Path::new("A.ts")]
+        );
+    }
+}
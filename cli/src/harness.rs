@@ -0,0 +1,117 @@
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::{bindings::Bindings, ModuleStyle, Options, Result};
+
+/// Filter matching the tests generated by `#[ts(export)]`.
+const EXPORT_TESTS: &str = "export_bindings_";
+
+/// The export harness of a crate, which are the `export_bindings_*` tests generated by
+/// `#[ts(export)]`.
+pub struct Harness {
+    args: Vec<String>,
+    /// Settings overriding the configuration of every crate, which are passed using environment
+    /// variables instead of features of ts-rs, since not every package depends on it directly
+    env: Vec<(&'static str, &'static str)>,
+}
+
+impl Harness {
+    pub fn new(options: &Options) -> Self {
+        let mut args = vec!["test".to_owned()];
+
+        if let Some(package) = &options.package {
+            args.extend(["--package".to_owned(), package.clone()]);
+        }
+        if options.workspace {
+            args.push("--workspace".to_owned());
+        }
+        if let Some(manifest_path) = &options.manifest_path {
+            args.push("--manifest-path".to_owned());
+            args.push(manifest_path.display().to_string());
+        }
+        if options.release {
+            args.push("--release".to_owned());
+        }
+        if let Some(profile) = &options.profile {
+            args.extend(["--profile".to_owned(), profile.clone()]);
+        }
+
+        if !options.features.is_empty() {
+            args.extend(["--features".to_owned(), options.features.join(",")]);
+        }
+
+        let mut env = vec![];
+        if options.format {
+            env.push(("TS_RS_FORMAT", "true"));
+        }
+        if options.module_style == ModuleStyle::Esm {
+            env.push(("TS_RS_IMPORT_EXTENSION", "js"));
+        }
+
+        args.push(EXPORT_TESTS.to_owned());
+        Self { args, env }
+    }
+
+    /// Builds and runs the harness, exporting the bindings into a scratch directory, and returns
+    /// them.
+    pub fn export(&self) -> Result<Bindings> {
+        let scratch = std::env::temp_dir().join(format!("cargo-ts-rs-{}", std::process::id()));
+        fs::remove_dir_all(&scratch).ok();
+        let result = self.run(&scratch);
+        let bindings = Bindings::read(&scratch);
+        fs::remove_dir_all(&scratch).ok();
+        result?;
+        bindings
+    }
+
+    /// Builds and runs the harness, exporting the bindings into `out_dir`.
+    fn run(&self, out_dir: &Path) -> Result<()> {
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+        let output = Command::new(cargo)
+            .args(&self.args)
+            .env("TS_RS_EXPORT_DIR", out_dir)
+            .envs(self.env.iter().copied())
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        std::io::stderr().write_all(&output.stdout)?;
+        Err(format!(
+            "`cargo {}` failed with {}",
+            self.args.join(" "),
+            output.status
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{Cargo, Command};
+
+    #[test]
+    fn workspace() {
+        let args = ["cargo", "ts-rs", "export", "--workspace", "--format"];
+        let Cargo::TsRs(cli) = Cargo::parse_from(args.into_iter().chain(["--module-style", "esm"]));
+        let Command::Export(options) = cli.command else {
+            unreachable!()
+        };
+        let harness = Harness::new(&options);
+
+        assert_eq!(harness.args, ["test", "--workspace", EXPORT_TESTS]);
+        assert_eq!(
+            harness.env,
+            [("TS_RS_FORMAT", "true"), ("TS_RS_IMPORT_EXTENSION", "js")]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! `cargo ts-rs` - export, check and clean the TypeScript bindings of a crate.
+//!
+//! The bindings are generated by running the `export_bindings_*` tests generated by
+//! `#[ts(export)]`, with `TS_RS_EXPORT_DIR` pointing to the output directory.
+//! `cargo ts-rs watch` does so again whenever the sources of the crate change.
+
+use std::{
+    error::Error,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::{bindings::Bindings, harness::Harness};
+
+mod bindings;
+mod harness;
+mod watch;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    #[command(name = "ts-rs", version, about)]
+    TsRs(Cli),
+}
+
+#[derive(Args)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export the bindings, reporting which files changed
+    Export(Options),
+    /// Check that the bindings on disk are up to date, without modifying them
+    Check(Options),
+    /// Export the bindings again whenever the sources change, only touching files which changed
+    Watch(WatchOptions),
+    /// Remove all files generated by ts-rs from the output directory
+    Clean {
+        /// Directory containing the bindings [default: the `out_dir` set in ts-rs.toml, or bindings]
+        #[arg(long, short)]
+        out_dir: Option<PathBuf>,
+        /// Path to Cargo.toml
+        #[arg(long)]
+        manifest_path: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct Options {
+    /// Directory the bindings are exported to [default: the `out_dir` set in ts-rs.toml, or bindings]
+    #[arg(long, short)]
+    out_dir: Option<PathBuf>,
+    /// Package to export the bindings of
+    #[arg(long, short)]
+    package: Option<String>,
+    /// Export the bindings of all packages in the workspace
+    #[arg(long)]
+    workspace: bool,
+    /// Path to Cargo.toml
+    #[arg(long)]
+    manifest_path: Option<PathBuf>,
+    /// Space or comma separated list of features to activate
+    #[arg(long, short = 'F')]
+    features: Vec<String>,
+    /// Build the export harness in release mode
+    #[arg(long, conflicts_with = "profile")]
+    release: bool,
+    /// Build the export harness with the given profile
+    #[arg(long)]
+    profile: Option<String>,
+    /// Format the bindings, using dprint if the `format` feature of ts-rs is enabled
+    #[arg(long)]
+    format: bool,
+    /// How import paths are written
+    #[arg(long, value_enum, default_value_t = ModuleStyle::Default)]
+    module_style: ModuleStyle,
+    /// Delete bindings which were generated by ts-rs, but are no longer exported
+    #[arg(long)]
+    prune: bool,
+}
+
+#[derive(Args)]
+struct WatchOptions {
+    #[command(flatten)]
+    options: Options,
+    /// Additional file or directory to watch, e.g a path dependency outside of the crate
+    #[arg(long, short)]
+    watch: Vec<PathBuf>,
+    /// How often the sources are checked for changes, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    poll_interval: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ModuleStyle {
+    /// `import type { User } from "./User"`
+    Default,
+    /// `import type { User } from "./User.js"`
+    Esm,
+}
+
+fn main() -> ExitCode {
+    let Cargo::TsRs(cli) = Cargo::parse();
+
+    match run(cli.command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command, returning `false` if the bindings are not up to date.
+fn run(command: Command) -> Result<bool> {
+    match command {
+        Command::Export(options) => {
+            let display_dir = configured_out_dir(&options.out_dir, &options.manifest_path)?;
+            let out_dir = absolute(&display_dir)?;
+            let exported = Harness::new(&options).export()?;
+
+            let (report, removed) = exported.write_to(&out_dir, options.prune)?;
+            bindings::print(&report, &removed, &display_dir);
+            Ok(true)
+        }
+        Command::Check(options) => {
+            let display_dir = configured_out_dir(&options.out_dir, &options.manifest_path)?;
+            let out_dir = absolute(&display_dir)?;
+            let expected = Harness::new(&options).export()?;
+
+            // the bindings are written into the ones on disk, which are only kept in memory
+            let config = load_config(&options.manifest_path)?;
+            let mut on_disk = Bindings::read(&out_dir)?.generated(&config).files;
+            let removed = (on_disk.keys())
+                .filter(|path| !expected.files.contains_key(*path))
+                .cloned()
+                .collect::<Vec<_>>();
+            let report = expected.write_into(&mut on_disk)?;
+            bindings::print(&report, &removed, &display_dir);
+            if report.changed().next().is_none() && removed.is_empty() {
+                Ok(true)
+            } else {
+                eprintln!("bindings are out of date, run `cargo ts-rs export` to update them");
+                Ok(false)
+            }
+        }
+        Command::Watch(options) => watch::run(&options),
+        Command::Clean {
+            out_dir,
+            manifest_path,
+        } => {
+            let out_dir = configured_out_dir(&out_dir, &manifest_path)?;
+            let absolute_dir = absolute(&out_dir)?;
+            let config = load_config(&manifest_path)?;
+            for path in Bindings::read(&absolute_dir)?
+                .generated(&config)
+                .files
+                .keys()
+            {
+                std::fs::remove_file(absolute_dir.join(path))?;
+                println!("{:>12} {}", "removed", out_dir.join(path).display());
+            }
+            remove_manifest(&absolute_dir)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Returns the directory passed using `--out-dir`, or the one configured for the crate
+fn configured_out_dir(
+    out_dir: &Option<PathBuf>,
+    manifest_path: &Option<PathBuf>,
+) -> Result<PathBuf> {
+    if let Some(out_dir) = out_dir {
+        return Ok(out_dir.clone());
+    }
+    Ok(load_config(manifest_path)?.export_dir())
+}
+
+/// Loads the configuration of the crate whose manifest is at `manifest_path`, or of the one in
+/// the working directory.
+fn load_config(manifest_path: &Option<PathBuf>) -> Result<ts_rs::Config> {
+    let dir = manifest_path
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+    Ok(ts_rs::Config::load(dir)?)
+}
+
+fn absolute(path: &Path) -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(path))
+}
+
+fn remove_manifest(out_dir: &Path) -> Result<()> {
+    match std::fs::remove_file(out_dir.join(bindings::MANIFEST)) {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn clean_manifest_path() {
+        let dir = std::env::temp_dir().join(format!("cargo-ts-rs-clean-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("generated")).unwrap();
+        let config = "out_dir = \"generated\"\nheader = \"// custom header\"\n";
+        fs::write(dir.join("ts-rs.toml"), config).unwrap();
+        fs::write(
+            dir.join("generated/A.ts"),
+            "// custom header\nexport type A = number;\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("generated/handwritten.ts"),
+            "export const a = 1;\n",
+        )
+        .unwrap();
+
+        let args = ["cargo", "ts-rs", "clean", "--manifest-path"];
+        let manifest_path = dir.join("Cargo.toml");
+        let Cargo::TsRs(cli) =
+            Cargo::parse_from(args.map(PathBuf::from).into_iter().chain([manifest_path]));
+        let cleaned = run(cli.command).unwrap();
+        let remaining = Bindings::read(&dir.join("generated")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(cleaned);
+        assert_eq!(
+            remaining.files.keys().collect::<Vec<_>>(),
+            [Path::new("handwritten.ts")]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{bindings, configured_out_dir, harness::Harness, Result, WatchOptions};
+
+/// Directories within the crate which never contain its sources
+const IGNORED: [&str; 2] = ["target", "node_modules"];
+
+/// Exports the bindings, and again whenever the sources change, until interrupted.
+///
+/// Every export runs the harness into a scratch directory first, and only then writes the bindings
+/// into the output directory, so that files which stayed the same are never touched.
+pub fn run(watch: &WatchOptions) -> Result<bool> {
+    let options = &watch.options;
+    let display_dir = configured_out_dir(&options.out_dir, &options.manifest_path)?;
+    fs::create_dir_all(&display_dir)?;
+    let out_dir = display_dir.canonicalize()?;
+
+    let crate_dir = match options.manifest_path.as_deref().and_then(Path::parent) {
+        Some(dir) if dir != Path::new("") => dir.to_owned(),
+        _ => PathBuf::from("."),
+    };
+    let mut watched = vec![Watched {
+        path: crate_dir.canonicalize()?,
+        only_sources: true,
+    }];
+    for path in &watch.watch {
+        watched.push(Watched {
+            path: path.canonicalize()?,
+            only_sources: false,
+        });
+    }
+
+    let export = Export {
+        harness: Harness::new(options),
+        out_dir,
+        display_dir,
+        prune: options.prune,
+    };
+    let interval = Duration::from_millis(watch.poll_interval);
+
+    let mut sources = Sources::scan(&watched, &export.out_dir)?;
+    loop {
+        if let Err(err) = export.run() {
+            eprintln!("error: {err}");
+        }
+        println!("{:>12} for changes", "watching");
+        sources = sources.changed(&watched, &export.out_dir, interval)?;
+    }
+}
+
+/// A file or directory whose changes trigger an export
+struct Watched {
+    path: PathBuf,
+    /// Only watch Rust sources and manifests within the directory, e.g to ignore the frontend
+    only_sources: bool,
+}
+
+struct Export {
+    harness: Harness,
+    out_dir: PathBuf,
+    display_dir: PathBuf,
+    prune: bool,
+}
+
+impl Export {
+    /// Runs the harness, writing the bindings into the output directory and reporting what
+    /// changed.
+    fn run(&self) -> Result<()> {
+        let start = Instant::now();
+        println!("{:>12} bindings", "exporting");
+
+        let exported = self.harness.export()?;
+        let (report, removed) = exported.write_to(&self.out_dir, self.prune)?;
+        bindings::print(&report, &removed, &self.display_dir);
+        println!(
+            "{:>12} in {:.2}s",
+            "finished",
+            start.elapsed().as_secs_f64()
+        );
+        Ok(())
+    }
+}
+
+/// The modification times of all watched files
+#[derive(Default, PartialEq, Eq)]
+struct Sources(BTreeMap<PathBuf, SystemTime>);
+
+impl Sources {
+    /// Reads the modification times of all files within `watched`, except for the ones within
+    /// `out_dir`.
+    fn scan(watched: &[Watched], out_dir: &Path) -> Result<Self> {
+        let mut sources = Self::default();
+        for watched in watched {
+            sources.read(&watched.path, watched, out_dir)?;
+        }
+        Ok(sources)
+    }
+
+    fn read(&mut self, path: &Path, watched: &Watched, out_dir: &Path) -> Result<()> {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            // the file was removed while scanning
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path)? {
+                let path = entry?.path();
+                if path != out_dir && !is_ignored(&path) {
+                    self.read(&path, watched, out_dir)?;
+                }
+            }
+        } else if !watched.only_sources || is_source(path) {
+            self.0.insert(path.to_owned(), metadata.modified()?);
+        }
+        Ok(())
+    }
+
+    /// Waits until the sources changed and then stayed the same for `interval`, so that saving
+    /// multiple files at once only triggers a single export.
+    fn changed(self, watched: &[Watched], out_dir: &Path, interval: Duration) -> Result<Self> {
+        let mut latest = self;
+        let mut changed = false;
+        loop {
+            thread::sleep(interval);
+            let next = Self::scan(watched, out_dir)?;
+            if next != latest {
+                latest = next;
+                changed = true;
+            } else if changed {
+                return Ok(latest);
+            }
+        }
+    }
+}
+
+/// Returns true for hidden directories and files, and directories which never contain sources
+fn is_ignored(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with('.') || IGNORED.contains(&name.as_ref())
+}
+
+/// Returns true for Rust sources, manifests and configuration files
+fn is_source(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("rs" | "toml")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources() {
+        let dir = std::env::temp_dir().join(format!("cargo-ts-rs-watch-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        for file in [
+            "Cargo.toml",
+            "src/lib.rs",
+            "README.md",
+            "bindings/User.ts",
+            "target/debug/build.rs",
+            ".git/config.toml",
+        ] {
+            let file = dir.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+
+        let watched = Watched {
+            path: dir.clone(),
+            only_sources: true,
+        };
+        let sources = Sources::scan(&[watched], &dir.join("bindings")).unwrap();
+        let files = sources.0.keys().cloned().collect::<Vec<_>>();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, [dir.join("Cargo.toml"), dir.join("src/lib.rs")]);
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use syn::{parse_quote, Attribute, Ident, ItemEnum, Path, Result, Type, WherePredicate};
 
-use super::{parse_assign_from_str, parse_bound, Attr, ContainerAttr, Serde};
+use super::{parse_assign_from_str, parse_bound, parse_optional_bool, Attr, ContainerAttr, Serde};
 use crate::{
     attr::{parse_assign_inflection, parse_assign_str, parse_concrete, Inflection},
     utils::{parse_attrs, parse_docs},
@@ -21,6 +21,7 @@ pub struct EnumAttr {
     pub docs: String,
     pub concrete: HashMap<Ident, Type>,
     pub bound: Option<Vec<WherePredicate>>,
+    pub readonly: Option<bool>,
     pub tag: Option<String>,
     pub untagged: bool,
     pub content: Option<String>,
@@ -91,6 +92,7 @@ impl Attr for EnumAttr {
                 (Some(bound), None) | (None, Some(bound)) => Some(bound),
                 (None, None) => None,
             },
+            readonly: self.readonly.or(other.readonly),
         }
     }
 
@@ -137,6 +139,13 @@ impl Attr for EnumAttr {
                     "`untagged` is not compatible with `type`"
                 );
             }
+
+            if self.readonly.is_some() {
+                syn_err_spanned!(
+                    item;
+                    "`readonly` is not compatible with `type`"
+                );
+            }
         }
 
         if self.type_as.is_some() {
@@ -219,6 +228,7 @@ impl_parse! {
         "untagged" => out.untagged = true,
         "concrete" => out.concrete = parse_concrete(input)?,
         "bound" => out.bound = Some(parse_bound(input)?),
+        "readonly" => out.readonly = Some(parse_optional_bool(input)?),
     }
 }
 
@@ -1,11 +1,13 @@
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{
-    AngleBracketedGenericArguments, Attribute, Field, GenericArgument, Ident, PathArguments, QSelf,
-    Result, ReturnType, Type, TypeArray, TypeGroup, TypeParen, TypePath, TypePtr, TypeReference,
-    TypeSlice, TypeTuple,
+    AngleBracketedGenericArguments, Attribute, Field, GenericArgument, Ident, Path, PathArguments,
+    QSelf, Result, ReturnType, Type, TypeArray, TypeGroup, TypeParen, TypePath, TypePtr,
+    TypeReference, TypeSlice, TypeTuple,
 };
 
 use super::{parse_assign_from_str, parse_assign_str, Attr, Serde};
-use crate::utils::{parse_attrs, parse_docs};
+use crate::utils::{contains_collection, parse_attrs, parse_docs, typed_array};
 
 #[derive(Default)]
 pub struct FieldAttr {
@@ -16,6 +18,8 @@ pub struct FieldAttr {
     pub skip: bool,
     pub optional: Optional,
     pub flatten: bool,
+    pub typed_array: bool,
+    pub es_collections: bool,
     pub docs: String,
 
     pub using_serde_with: bool,
@@ -44,6 +48,23 @@ impl FieldAttr {
         Ok(result)
     }
 
+    /// Wraps `expr`, an expression rendering the type of this field, so that it is evaluated with
+    /// the render options requested by the attributes of this field.
+    pub fn render_scope(&self, crate_rename: &Path, expr: TokenStream) -> TokenStream {
+        if !self.typed_array && !self.es_collections {
+            return expr;
+        }
+
+        let typed_arrays = self.typed_array.then(|| quote!(.typed_arrays(true)));
+        let es_collections = self.es_collections.then(|| quote!(.es_collections(true)));
+        quote! {
+            #crate_rename::RenderOptions::current()
+                #typed_arrays
+                #es_collections
+                .scope(|| #expr)
+        }
+    }
+
     pub fn type_as(&self, original_type: &Type) -> Type {
         if let Some(mut ty) = self.type_as.clone() {
             replace_underscore(&mut ty, original_type);
@@ -69,6 +90,8 @@ impl Attr for FieldAttr {
                 nullable: self.optional.nullable || other.optional.nullable,
             },
             flatten: self.flatten || other.flatten,
+            typed_array: self.typed_array || other.typed_array,
+            es_collections: self.es_collections || other.es_collections,
 
             using_serde_with: self.using_serde_with || other.using_serde_with,
 
@@ -111,6 +134,40 @@ impl Attr for FieldAttr {
             }
         }
 
+        if self.typed_array {
+            if self.type_override.is_some() {
+                syn_err_spanned!(field; "`type` is not compatible with `typed_array`")
+            }
+
+            if self.flatten {
+                syn_err_spanned!(field; "`flatten` is not compatible with `typed_array`")
+            }
+
+            if typed_array(&self.type_as(&field.ty)).is_none() {
+                syn_err_spanned!(
+                    field;
+                    "`typed_array` can only be used on sequences of numeric primitives, like `Vec<u8>`, `[f32; N]` or `bytes::Bytes`"
+                )
+            }
+        }
+
+        if self.es_collections {
+            if self.type_override.is_some() {
+                syn_err_spanned!(field; "`type` is not compatible with `es_collections`")
+            }
+
+            if self.flatten {
+                syn_err_spanned!(field; "`flatten` is not compatible with `es_collections`")
+            }
+
+            if !contains_collection(&self.type_as(&field.ty)) {
+                syn_err_spanned!(
+                    field;
+                    "`es_collections` can only be used on types containing maps or sets, like `HashMap<K, V>` or `BTreeSet<T>`"
+                )
+            }
+        }
+
         if self.flatten {
             if self.type_as.is_some() {
                 syn_err_spanned!(
@@ -193,6 +250,8 @@ impl_parse! {
             }
         },
         "flatten" => out.flatten = true,
+        "typed_array" => out.typed_array = true,
+        "es_collections" => out.es_collections = true,
     }
 }
 
@@ -169,6 +169,18 @@ where
     }
 }
 
+fn parse_optional_bool(input: ParseStream) -> Result<bool> {
+    if !input.peek(Token![=]) {
+        return Ok(true);
+    }
+
+    input.parse::<Token![=]>()?;
+    match Lit::parse(input)? {
+        Lit::Bool(bool) => Ok(bool.value()),
+        other => Err(Error::new(other.span(), "expected `true` or `false`")),
+    }
+}
+
 fn parse_bound(input: ParseStream) -> Result<Vec<WherePredicate>> {
     input.parse::<Token![=]>()?;
     match Lit::parse(input)? {
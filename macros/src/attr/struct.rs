@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use syn::{parse_quote, Attribute, Fields, Ident, Path, Result, Type, WherePredicate};
 
 use super::{
-    parse_assign_from_str, parse_assign_inflection, parse_bound, parse_concrete, Attr,
-    ContainerAttr, Serde, Tagged,
+    parse_assign_from_str, parse_assign_inflection, parse_bound, parse_concrete,
+    parse_optional_bool, Attr, ContainerAttr, Serde, Tagged,
 };
 use crate::{
     attr::{parse_assign_str, EnumAttr, Inflection, VariantAttr},
@@ -24,6 +24,9 @@ pub struct StructAttr {
     pub docs: String,
     pub concrete: HashMap<Ident, Type>,
     pub bound: Option<Vec<WherePredicate>>,
+    pub readonly: Option<bool>,
+    pub interface: Option<bool>,
+    pub brand: bool,
 }
 
 impl StructAttr {
@@ -63,6 +66,8 @@ impl StructAttr {
                 },
                 _ => None,
             },
+            // variants are never declared on their own
+            interface: Some(false),
 
             // inline and skip are not supported on StructAttr
             ..Self::default()
@@ -90,6 +95,9 @@ impl Attr for StructAttr {
                 (Some(bound), None) | (None, Some(bound)) => Some(bound),
                 (None, None) => None,
             },
+            readonly: self.readonly.or(other.readonly),
+            interface: self.interface.or(other.interface),
+            brand: self.brand || other.brand,
         }
     }
 
@@ -106,6 +114,18 @@ impl Attr for StructAttr {
             if self.tag.is_some() {
                 syn_err!("`tag` is not compatible with `type`");
             }
+
+            if self.readonly.is_some() {
+                syn_err!("`readonly` is not compatible with `type`");
+            }
+
+            if self.interface.is_some() {
+                syn_err!("`interface` is not compatible with `type`");
+            }
+
+            if self.brand {
+                syn_err!("`brand` is not compatible with `type`");
+            }
         }
 
         if self.type_as.is_some() {
@@ -116,6 +136,14 @@ impl Attr for StructAttr {
             if self.rename_all.is_some() {
                 syn_err!("`rename_all` is not compatible with `as`");
             }
+
+            if self.interface.is_some() {
+                syn_err!("`interface` is not compatible with `as`");
+            }
+
+            if self.brand {
+                syn_err!("`brand` is not compatible with `as`");
+            }
         }
 
         if !matches!(item, Fields::Named(_)) {
@@ -126,6 +154,14 @@ impl Attr for StructAttr {
             if self.rename_all.is_some() {
                 syn_err!("`rename_all` cannot be used with unit or tuple structs");
             }
+
+            if self.interface == Some(true) {
+                syn_err!("`interface` cannot be used with unit or tuple structs");
+            }
+        }
+
+        if self.brand && !matches!(item, Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+            syn_err!("`brand` can only be used on newtype structs");
         }
 
         Ok(())
@@ -152,6 +188,9 @@ impl_parse! {
         "export_to" => out.export_to = Some(parse_assign_str(input)?),
         "concrete" => out.concrete = parse_concrete(input)?,
         "bound" => out.bound = Some(parse_bound(input)?),
+        "readonly" => out.readonly = Some(parse_optional_bool(input)?),
+        "interface" => out.interface = Some(parse_optional_bool(input)?),
+        "brand" => out.brand = true,
     }
 }
 
@@ -25,6 +25,14 @@ enum Dependency {
         ty: Rc<Type>,
     },
     Type(Rc<Type>),
+    // A dependency on `ty`, which is only used if the type depending on it is an object type.
+    // This is the case for types an interface `extends`. If `configured`, the type is only
+    // declared as an interface if enabled by the configuration.
+    Extends {
+        crate_rename: Rc<Path>,
+        ty: Rc<Type>,
+        configured: bool,
+    },
 }
 
 impl Dependencies {
@@ -59,6 +67,17 @@ impl Dependencies {
         });
     }
 
+    /// Adds the given type, which is extended by an interface. If `configured`, the type depending
+    /// on it is only declared as an interface if the `interface` setting is enabled.
+    pub fn push_extends(&mut self, ty: &Type, configured: bool) {
+        let ty = self.push_type(ty);
+        self.dependencies.insert(Dependency::Extends {
+            crate_rename: self.crate_rename.clone(),
+            ty,
+            configured,
+        });
+    }
+
     pub fn append(&mut self, other: Dependencies) {
         self.dependencies.extend(other.dependencies);
         self.types.extend(other.types);
@@ -97,6 +116,22 @@ impl ToTokens for Dependency {
                 quote![<#ty as #crate_rename::TS>::visit_generics(v)]
             }
             Dependency::Type(ty) => quote![v.visit::<#ty>()],
+            Dependency::Extends {
+                crate_rename,
+                ty,
+                configured,
+            } => {
+                let enabled = match configured {
+                    true => quote!(#crate_rename::RenderOptions::current().interface),
+                    false => quote!(true),
+                };
+                quote! {
+                    if #enabled && <Self as #crate_rename::TS>::OBJECT {
+                        v.visit::<#ty>();
+                        <#ty as #crate_rename::TS>::visit_generics(v);
+                    }
+                }
+            }
         });
     }
 }
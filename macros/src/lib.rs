@@ -30,11 +30,25 @@ struct DerivedTS {
     concrete: HashMap<Ident, Type>,
     bound: Option<Vec<WherePredicate>>,
     schema: Option<schem::Schema>,
+    object: Option<Object>,
+    // for branded newtypes, an expression which evaluates to the type without its brand
+    brand: Option<TokenStream>,
 
     export: bool,
     export_to: Option<String>,
 }
 
+/// A type represented as an object type, e.g. `{ a: number, b: string }`, which can be declared as
+/// an interface.
+struct Object {
+    /// Expression which evaluates to whether the type is an object type with statically known
+    /// members. See `TS::OBJECT`.
+    is_object: TokenStream,
+    /// If the type is declared as an interface, an expression which evaluates to the interface
+    /// body, including its `extends` clause.
+    interface: Option<TokenStream>,
+}
+
 impl DerivedTS {
     fn into_impl(mut self, rust_ty: Ident, generics: Generics) -> TokenStream {
         #[cfg(feature = "default-export")]
@@ -42,21 +56,39 @@ impl DerivedTS {
         #[cfg(not(feature = "default-export"))]
         let default_export = false;
 
-        let export =
-            (self.export || default_export).then(|| self.generate_export_test(&rust_ty, &generics));
+        let export = (self.export || default_export).then(|| {
+            let test = self.generate_export_test(&rust_ty, &generics);
+            let registration = self.generate_registration(&rust_ty, &generics);
+            quote!(#test #registration)
+        });
 
         let output_path_fn = {
+            let crate_rename = &self.crate_rename;
+            let name = &self.ts_name;
+
+            let crate_dir = quote!(option_env!("CARGO_MANIFEST_DIR"));
+
+            // unless a file is given by `export_to`, its name is derived from the name of the
+            // type, following the case configured in `ts-rs.toml` of this crate
             let path = match self.export_to.as_deref() {
                 Some(dirname) if dirname.ends_with('/') => {
-                    format!("{}{}.ts", dirname, self.ts_name)
+                    quote!(#crate_rename::type_output_path(#crate_dir, #dirname, #name))
+                }
+                Some(filename) => quote!(std::path::Path::new(#filename)),
+                // with `module-paths`, the directory is derived from the module of the type
+                None if cfg!(feature = "module-paths") => {
+                    quote!(#crate_rename::module_output_path(#crate_dir, module_path!(), #name))
                 }
-                Some(filename) => filename.to_owned(),
-                None => format!("{}.ts", self.ts_name),
+                None => quote!(#crate_rename::type_output_path(#crate_dir, "", #name)),
             };
 
             quote! {
                 fn output_path() -> Option<&'static std::path::Path> {
-                    Some(std::path::Path::new(#path))
+                    Some(#path)
+                }
+
+                fn crate_dir() -> Option<&'static str> {
+                    #crate_dir
                 }
             }
         };
@@ -66,6 +98,11 @@ impl DerivedTS {
             docs => Some(quote!(const DOCS: Option<&'static str> = Some(#docs);)),
         };
 
+        let object = self
+            .object
+            .as_ref()
+            .map(|Object { is_object, .. }| quote!(const OBJECT: bool = #is_object;));
+
         let crate_rename = self.crate_rename.clone();
 
         let ident = self.ts_name.clone();
@@ -94,6 +131,7 @@ impl DerivedTS {
                 }
 
                 #docs
+                #object
                 #name
                 #decl
                 #inline
@@ -121,11 +159,38 @@ impl DerivedTS {
         final_q
     }
 
+    /// Renders the type with `readonly` turned on or off, overriding the default set by the
+    /// `readonly` setting of the configuration.
+    fn readonly(mut self, readonly: Option<bool>) -> Self {
+        let readonly = match readonly {
+            Some(readonly) => readonly,
+            None => return self,
+        };
+
+        let crate_rename = &self.crate_rename;
+        let options = quote!(#crate_rename::RenderOptions::current().readonly(#readonly));
+        let scope = |expr: &TokenStream| quote!(#options.scope(|| #expr));
+        self.inline = scope(&self.inline);
+        self.inline_flattened = self.inline_flattened.as_ref().map(scope);
+        if let Some(interface) = self.object.as_mut().and_then(|o| o.interface.as_mut()) {
+            *interface = scope(interface);
+        }
+        if let Some(brand) = self.brand.as_mut() {
+            *brand = scope(brand);
+        }
+        self
+    }
+
     /// Returns an expression which evaluates to the TypeScript name of the type, including generic
     /// parameters.
     fn name_with_generics(&self, generics: &Generics) -> TokenStream {
-        let name = &self.ts_name;
         let crate_rename = &self.crate_rename;
+        let name = &self.ts_name;
+        // with `import-aliases`, the type may be imported under a different name
+        let name = match cfg!(feature = "import-aliases") {
+            true => quote!(#crate_rename::aliased::<Self>(#name)),
+            false => quote!(#name),
+        };
         let mut generics_ts_names = generics
             .type_params()
             .filter(|ty| !self.concrete.contains_key(&ty.ident))
@@ -207,6 +272,30 @@ impl DerivedTS {
         }
     }
 
+    /// Registers the type in the global registry, so it can be exported using
+    /// `ts_rs::export_registered`.
+    ///
+    /// Whether the registry is enabled is decided by the features of ts-rs, not the ones of this
+    /// crate, which may be unified with those of a build-dependency on ts-rs.
+    fn generate_registration(&self, rust_ty: &Ident, generics: &Generics) -> TokenStream {
+        let crate_rename = &self.crate_rename;
+        let generic_params = generics
+            .type_params()
+            .map(|ty| match self.concrete.get(&ty.ident) {
+                None => quote! { #crate_rename::Dummy },
+                Some(ty) => quote! { #ty },
+            });
+        let name = rust_ty.to_string().replace("r#", "");
+
+        quote! {
+            #crate_rename::__register! {
+                #crate_rename::Registration::new::<#rust_ty<#(#generic_params),*>>(
+                    concat!(module_path!(), "::", #name)
+                )
+            }
+        }
+    }
+
     fn generate_generics_fn(&self, generics: &Generics) -> TokenStream {
         let crate_rename = &self.crate_rename;
         let generics = generics
@@ -560,11 +649,12 @@ impl DerivedTS {
                 fn schema(export: bool) -> String {
                     #(#def_generics)*
                     #(#def_dependencies)*
+                    let schema = #crate_rename::RenderOptions::current().resolve_marks(#schema);
                     let mut schem = "".to_string();
                     if (export) {
-                        schem = format!("const {} = {}", #name, #schema);
+                        schem = format!("const {} = {}", #name, schema);
                     } else {
-                        schem = format!("{}", #schema);
+                        schem = format!("{}", schema);
                     }
                     #(#repl_dependencies)*
                     #(#repl_generics)*
@@ -655,14 +745,54 @@ impl DerivedTS {
             // use instead. This might be something to change in the future.
             G::Const(ConstParam { ident, .. }) => Some(quote!(#ident)),
         });
+        // Interfaces are only declared if the type is an object type, so `interface` evaluates to
+        // `None` otherwise. We then fall back to a type alias.
+        let interface = self
+            .object
+            .as_ref()
+            .and_then(|object| object.interface.as_ref())
+            .map(|interface| quote!(if let Some(interface) = #interface));
+        let interface_decl_concrete = interface.as_ref().map(|interface| {
+            quote! {
+                #interface {
+                    return format!("interface {} {interface}", #name);
+                }
+            }
+        });
+        let interface_decl = interface.as_ref().map(|interface| {
+            quote! {
+                #interface {
+                    return format!("interface {}{generics} {interface}", #name);
+                }
+            }
+        });
+        // Branded types come with a function creating them from the type without a brand
+        let constructor = self.brand.as_ref().map(|brand| {
+            let generic_idents = generic_idents.clone();
+            quote! {
+                fn constructor() -> Option<String> {
+                    #generic_types
+                    let generics = #ts_generics;
+                    let name = <#rust_ty<#(#generic_idents,)*> as #crate_rename::TS>::name();
+                    let brand = #brand;
+                    Some(format!(
+                        "const {} = {generics}(value: {brand}): {name} => value as {name};",
+                        #name
+                    ))
+                }
+            }
+        });
         quote! {
+            #constructor
             fn decl_concrete() -> String {
+                #interface_decl_concrete
                 format!("type {} = {};", #name, <Self as #crate_rename::TS>::inline())
             }
             fn decl() -> String {
                 #generic_types
-                let inline = <#rust_ty<#(#generic_idents,)*> as #crate_rename::TS>::inline();
                 let generics = #ts_generics;
+                #interface_decl
+                let inline = <#rust_ty<#(#generic_idents,)*> as #crate_rename::TS>::inline();
                 format!("type {}{generics} = {inline};", #name)
             }
         }
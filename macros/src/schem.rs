@@ -3,6 +3,11 @@ use std::collections::{HashMap, HashSet};
 use quote::ToTokens;
 use syn::{Expr, Fields, GenericArgument, Ident, PathArguments, Token, Type};
 
+use crate::{
+    attr::FieldAttr,
+    utils::{contains_collection, typed_array},
+};
+
 #[derive(PartialEq, Debug)]
 pub enum SchemaType {
     Enum,
@@ -20,6 +25,10 @@ pub enum SchemaFieldRef {
 pub struct SchemaField {
     name: String,
     sref: SchemaFieldRef,
+    /// The JavaScript typed array decoders should read this field into, e.g. `Uint8Array`
+    typed_array: Option<Mark<&'static str>>,
+    /// Whether decoders should build an ES `Map` or `Set` for the maps and sets in this field
+    es_collections: Option<Mark<()>>,
 }
 
 #[derive(Debug)]
@@ -29,6 +38,50 @@ pub struct SchemaVariant {
     discriminant: Option<i32>,
 }
 
+/// Whether a field is marked for a JavaScript representation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mark<T> {
+    /// Requested for the field using an attribute, e.g. `#[ts(typed_array)]`
+    Requested(T),
+    /// Depends on a setting of the configuration of the crate, e.g. `typed_arrays = true`, which
+    /// is only known once the schema is rendered
+    Configured(T),
+}
+
+impl<T> Mark<T> {
+    fn value(&self) -> &T {
+        match self {
+            Mark::Requested(value) | Mark::Configured(value) => value,
+        }
+    }
+
+    /// Wraps `entry` into a mark resolved by `RenderOptions::resolve_marks`, unless it was
+    /// requested
+    fn render(&self, setting: &str, entry: String) -> String {
+        match self {
+            Mark::Requested(_) => entry,
+            Mark::Configured(_) => format!("%%{}:{}%%", setting, entry),
+        }
+    }
+}
+
+impl SchemaField {
+    /// The `"typedArray"` and `"esCollections"` entries of this field, telling decoders which
+    /// JavaScript representation to build
+    fn repr_entries(&self, indent: &str) -> String {
+        let mut entries = String::new();
+        if let Some(typed_array) = &self.typed_array {
+            let entry = format!(",\n{}\"typedArray\": \"{}\"", indent, typed_array.value());
+            entries.push_str(&typed_array.render("typed_arrays", entry));
+        }
+        if let Some(es_collections) = &self.es_collections {
+            let entry = format!(",\n{}\"esCollections\": true", indent);
+            entries.push_str(&es_collections.render("es_collections", entry));
+        }
+        entries
+    }
+}
+
 impl SchemaFieldRef {
     pub fn to_string(&self) -> String {
         match self {
@@ -156,11 +209,21 @@ impl Schema {
                 Some(ident) => ident.to_string(),
                 None => "".to_string(),
             };
-            self.add_variant_field(name, &field.ty);
+            // Invalid attributes are reported when the variant itself is formatted
+            let (typed_array, es_collections) = FieldAttr::from_attrs(&field.attrs)
+                .map(|attr| (attr.typed_array, attr.es_collections))
+                .unwrap_or_default();
+            self.add_variant_field(name, &field.ty, typed_array, es_collections);
         }
     }
 
-    pub fn add_variant_field(&mut self, name: String, stype: &Type) {
+    pub fn add_variant_field(
+        &mut self,
+        name: String,
+        stype: &Type,
+        typed_array: bool,
+        es_collections: bool,
+    ) {
         self.process_type(stype);
         self.variants.last_mut().unwrap().fields.push(SchemaField {
             name,
@@ -171,6 +234,8 @@ impl Schema {
                 Type::Path(t) => SchemaFieldRef::Refs(format!("{}", remove_create_type_path(t))),
                 _ => SchemaFieldRef::Type(stype.to_token_stream().to_string()),
             },
+            typed_array: typed_array_mark(stype, typed_array),
+            es_collections: es_collections_mark(stype, es_collections),
         });
     }
 
@@ -200,8 +265,18 @@ impl Schema {
 
             if let Some(last_segment) = type_path.path.segments.last() {
                 let ident = last_segment.ident.to_string();
-                if ident == "Option" || ident == "Vec" || ident == "Result" || ident == "HashMap"
-                /* ajoutez d'autres types génériques si nécessaire */
+                if matches!(
+                    ident.as_str(),
+                    "Option"
+                        | "Vec"
+                        | "Result"
+                        | "HashMap"
+                        | "BTreeMap"
+                        | "IndexMap"
+                        | "HashSet"
+                        | "BTreeSet"
+                        | "IndexSet"
+                ) /* ajoutez d'autres types génériques si nécessaire */
                 {
                     if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
                         for arg in &args.args {
@@ -251,7 +326,13 @@ impl Schema {
         // Ajoutez d'autres cas si nécessaire (par exemple, Type::Array)
     }
 
-    pub fn add_field(&mut self, name: String, stype: &Type) {
+    pub fn add_field(
+        &mut self,
+        name: String,
+        stype: &Type,
+        typed_array: bool,
+        es_collections: bool,
+    ) {
         self.process_type(stype);
         self.fields.push(SchemaField {
             name,
@@ -262,6 +343,8 @@ impl Schema {
                 Type::Path(t) => SchemaFieldRef::Refs(format!("{}", remove_create_type_path(t))),
                 _ => SchemaFieldRef::Type(stype.to_token_stream().to_string()),
             },
+            typed_array: typed_array_mark(stype, typed_array),
+            es_collections: es_collections_mark(stype, es_collections),
         });
     }
 
@@ -290,8 +373,10 @@ impl Schema {
                     replace_types(&sref, &hashmap_to_hashset(self.def.clone()), &self.generics, &self.parent_generics)
                         .replace(" ", "");
                 s.push_str(&format!(
-                    "    {{\n      \"name\": \"{}\",\n      \"type\": \"{}\"\n    }},\n",
-                    field.name, final_type
+                    "    {{\n      \"name\": \"{}\",\n      \"type\": \"{}\"{}\n    }},\n",
+                    field.name,
+                    final_type,
+                    field.repr_entries("      "),
                 ));
             }
             s.push_str("  ],\n");
@@ -321,9 +406,10 @@ impl Schema {
                         replace_types(&sref, &hashmap_to_hashset(self.def.clone()), &self.generics, &self.parent_generics)
                             .replace(" ", "");
                     s.push_str(&format!(
-                "        {{\n          \"name\": \"{}\",\n          \"type\": \"{}\"\n        }},\n",
+                "        {{\n          \"name\": \"{}\",\n          \"type\": \"{}\"{}\n        }},\n",
                 name,
                 final_type,
+                field.repr_entries("          "),
             ));
                     index += 1;
                 }
@@ -428,6 +514,24 @@ impl Schema {
     }
 }
 
+/// Typed arrays are marked for fields annotated with `#[ts(typed_array)]`, or for all fields if
+/// `typed_arrays` is enabled in the configuration.
+fn typed_array_mark(stype: &Type, requested: bool) -> Option<Mark<&'static str>> {
+    typed_array(stype).map(|array| match requested {
+        true => Mark::Requested(array),
+        false => Mark::Configured(array),
+    })
+}
+
+/// ES collections are marked for fields annotated with `#[ts(es_collections)]`, or for all fields
+/// if `es_collections` is enabled in the configuration.
+fn es_collections_mark(stype: &Type, requested: bool) -> Option<Mark<()>> {
+    contains_collection(stype).then_some(match requested {
+        true => Mark::Requested(()),
+        false => Mark::Configured(()),
+    })
+}
+
 fn extract_type_names(sref: &str) -> Vec<String> {
     let mut type_names = Vec::new();
     let mut chars = sref.chars().peekable();
@@ -863,12 +967,12 @@ mod tests {
     #[test]
     fn test_add_field() {
         let mut schema = super::Schema::new("MyObject".to_string(), super::SchemaType::Struct);
-        schema.add_field("id".to_string(), &syn::parse_quote!(usize));
-        schema.add_field("name".to_string(), &syn::parse_quote!(String));
-        schema.add_field("age".to_string(), &syn::parse_quote!(u8));
-        schema.add_field("is_active".to_string(), &syn::parse_quote!(bool));
-        schema.add_field("created_at".to_string(), &syn::parse_quote!(NaiveDateTime));
-        schema.add_field("updated_at".to_string(), &syn::parse_quote!(NaiveDateTime));
+        schema.add_field("id".to_string(), &syn::parse_quote!(usize), false, false);
+        schema.add_field("name".to_string(), &syn::parse_quote!(String), false, false);
+        schema.add_field("age".to_string(), &syn::parse_quote!(u8), false, false);
+        schema.add_field("is_active".to_string(), &syn::parse_quote!(bool), false, false);
+        schema.add_field("created_at".to_string(), &syn::parse_quote!(NaiveDateTime), false, false);
+        schema.add_field("updated_at".to_string(), &syn::parse_quote!(NaiveDateTime), false, false);
         assert_eq!(schema.fields.len(), 6);
         assert_eq!(schema.fields[0].name, "id");
         assert_eq!(schema.fields[1].name, "name");
@@ -880,6 +984,42 @@ mod tests {
         // TODO: Verify is coherence with the expected result
     }
 
+    #[test]
+    fn test_typed_array_field() {
+        use super::Mark;
+
+        let mut schema = super::Schema::new("Image".to_string(), super::SchemaType::Struct);
+        schema.add_field("pixels".to_string(), &syn::parse_quote!(Vec<u8>), true, false);
+        schema.add_field("depth".to_string(), &syn::parse_quote!(Option<[f32; 4]>), true, false);
+        schema.add_field("name".to_string(), &syn::parse_quote!(String), true, false);
+        schema.add_field("labels".to_string(), &syn::parse_quote!(Vec<u8>), false, false);
+        assert_eq!(schema.fields[0].typed_array, Some(Mark::Requested("Uint8Array")));
+        assert_eq!(schema.fields[1].typed_array, Some(Mark::Requested("Float32Array")));
+        assert_eq!(schema.fields[2].typed_array, None);
+        assert_eq!(schema.fields[3].typed_array, Some(Mark::Configured("Uint8Array")));
+        let schema = schema.to_string();
+        assert!(schema.contains("\"type\": \"Vec<u8>\",\n      \"typedArray\": \"Uint8Array\"\n"));
+        assert!(schema.contains("%%typed_arrays:,\n      \"typedArray\": \"Uint8Array\"%%"));
+    }
+
+    #[test]
+    fn test_es_collections_field() {
+        use super::Mark;
+
+        let mut schema = super::Schema::new("Graph".to_string(), super::SchemaType::Struct);
+        schema.add_field("edges".to_string(), &syn::parse_quote!(HashMap<Node, Vec<Node>>), false, true);
+        schema.add_field("roots".to_string(), &syn::parse_quote!(Option<BTreeSet<Node>>), false, true);
+        schema.add_field("name".to_string(), &syn::parse_quote!(String), false, true);
+        schema.add_field("labels".to_string(), &syn::parse_quote!(HashSet<String>), false, false);
+        assert_eq!(schema.fields[0].es_collections, Some(Mark::Requested(())));
+        assert_eq!(schema.fields[1].es_collections, Some(Mark::Requested(())));
+        assert_eq!(schema.fields[2].es_collections, None);
+        assert_eq!(schema.fields[3].es_collections, Some(Mark::Configured(())));
+        let schema = schema.to_string();
+        assert_eq!(schema.matches("\"esCollections\": true").count(), 3);
+        assert!(schema.contains("%%es_collections:,\n      \"esCollections\": true%%"));
+    }
+
     #[test]
     fn test_add_variant() {
         // TODO: Add tests
@@ -889,8 +1029,8 @@ mod tests {
     fn test_to_string() {
         // Create a schema with a struct
         let mut schema = super::Schema::new("MyObject".to_string(), super::SchemaType::Struct);
-        schema.add_field("id".to_string(), &syn::parse_quote!(usize));
-        schema.add_field("name".to_string(), &syn::parse_quote!(String));
+        schema.add_field("id".to_string(), &syn::parse_quote!(usize), false, false);
+        schema.add_field("name".to_string(), &syn::parse_quote!(String), false, false);
 
         let expected = r#"{
             "type": "struct",
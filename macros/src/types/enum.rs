@@ -37,7 +37,8 @@ pub(crate) fn r#enum_def(s: &ItemEnum) -> syn::Result<DerivedTS> {
     }
 
     if let Some(attr_type_as) = &enum_attr.type_as {
-        return type_as::type_as_enum(&enum_attr, &name, attr_type_as);
+        return type_as::type_as_enum(&enum_attr, &name, attr_type_as)
+            .map(|ts| ts.readonly(enum_attr.readonly));
     }
 
     if s.variants.is_empty() {
@@ -57,7 +58,7 @@ pub(crate) fn r#enum_def(s: &ItemEnum) -> syn::Result<DerivedTS> {
         )?;
     }
 
-    Ok(DerivedTS {
+    let ts = DerivedTS {
         crate_rename,
         inline: quote!([#(#formatted_variants),*].join(" | ")),
         inline_flattened: Some(quote!(
@@ -71,7 +72,11 @@ pub(crate) fn r#enum_def(s: &ItemEnum) -> syn::Result<DerivedTS> {
         concrete: enum_attr.concrete,
         bound: enum_attr.bound,
         schema: Some(schema),
-    })
+        object: None,
+        brand: None,
+    };
+
+    Ok(ts.readonly(enum_attr.readonly))
 }
 
 fn format_variant(
@@ -82,6 +87,7 @@ fn format_variant(
     variant: &Variant,
 ) -> syn::Result<()> {
     let crate_rename = enum_attr.crate_rename();
+    let readonly = quote!(#crate_rename::RenderOptions::current().readonly_modifier());
 
     // If `variant.fields` is not a `Fields::Named(_)` the `rename_all_fields`
     // attribute must be ignored to prevent a `rename_all` from getting to
@@ -145,11 +151,11 @@ fn format_variant(
                 if field_attr.skip {
                     quote!(format!("\"{}\"", #name))
                 } else {
-                    quote!(format!("{{ \"{}\": {} }}", #name, #parsed_ty))
+                    quote!(format!("{{ {}\"{}\": {} }}", #readonly, #name, #parsed_ty))
                 }
             }
             _ => {
-                quote!(format!("{{ \"{}\": {} }}", #name, #parsed_ty))
+                quote!(format!("{{ {}\"{}\": {} }}", #readonly, #name, #parsed_ty))
             }
         },
         (false, Tagged::Adjacently { tag, content }) => match &variant.fields {
@@ -160,7 +166,7 @@ fn format_variant(
                 field_attr.assert_validity(field)?;
 
                 if field_attr.skip {
-                    quote!(format!("{{ \"{}\": \"{}\" }}", #tag, #name))
+                    quote!(format!("{{ {}\"{}\": \"{}\" }}", #readonly, #tag, #name))
                 } else {
                     let ty = match field_attr.type_override {
                         Some(type_override) => quote!(#type_override),
@@ -169,13 +175,15 @@ fn format_variant(
                             quote!(<#ty as #crate_rename::TS>::name())
                         }
                     };
-                    quote!(format!("{{ \"{}\": \"{}\", \"{}\": {} }}", #tag, #name, #content, #ty))
+                    quote!(
+                        format!("{{ {}\"{}\": \"{}\", {}\"{}\": {} }}", #readonly, #tag, #name, #readonly, #content, #ty)
+                    )
                 }
             }
-            Fields::Unit => quote!(format!("{{ \"{}\": \"{}\" }}", #tag, #name)),
+            Fields::Unit => quote!(format!("{{ {}\"{}\": \"{}\" }}", #readonly, #tag, #name)),
             _ => {
                 quote!(
-                    format!("{{ \"{}\": \"{}\", \"{}\": {} }}", #tag, #name, #content, #parsed_ty)
+                    format!("{{ {}\"{}\": \"{}\", {}\"{}\": {} }}", #readonly, #tag, #name, #readonly, #content, #parsed_ty)
                 )
             }
         },
@@ -191,7 +199,7 @@ fn format_variant(
                     field_attr.assert_validity(field)?;
 
                     if field_attr.skip {
-                        quote!(format!("{{ \"{}\": \"{}\" }}", #tag, #name))
+                        quote!(format!("{{ {}\"{}\": \"{}\" }}", #readonly, #tag, #name))
                     } else {
                         let ty = match field_attr.type_override {
                             Some(type_override) => quote! { #type_override },
@@ -201,14 +209,16 @@ fn format_variant(
                             }
                         };
 
-                        quote!(format!("{{ \"{}\": \"{}\" }} & {}", #tag, #name, #ty))
+                        quote!(format!("{{ {}\"{}\": \"{}\" }} & {}", #readonly, #tag, #name, #ty))
                     }
                 }
                 Fields::Unit => {
-                    quote!(format!("{{ \"{}\": \"{}\" }}", #tag, #name))
+                    quote!(format!("{{ {}\"{}\": \"{}\" }}", #readonly, #tag, #name))
                 }
                 _ => {
-                    quote!(format!("{{ \"{}\": \"{}\" }} & {}", #tag, #name, #parsed_ty))
+                    quote!(
+                        format!("{{ {}\"{}\": \"{}\" }} & {}", #readonly, #tag, #name, #parsed_ty)
+                    )
                 }
             },
         },
@@ -234,5 +244,7 @@ fn empty_enum(name: impl Into<String>, enum_attr: EnumAttr) -> DerivedTS {
         concrete: enum_attr.concrete,
         bound: enum_attr.bound,
         schema: None,
+        object: None,
+        brand: None,
     }
 }
@@ -17,7 +17,7 @@ pub(crate) use r#enum::r#enum_def;
 
 pub(crate) fn struct_def(s: &ItemStruct) -> Result<DerivedTS> {
     let attr = StructAttr::from_attrs(&s.attrs)?;
-    type_def(&attr, &s.ident.to_string(), &s.fields, &s.generics)
+    Ok(type_def(&attr, &s.ident.to_string(), &s.fields, &s.generics)?.readonly(attr.readonly))
 }
 
 fn type_def(
@@ -1,210 +1,266 @@
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{
-    spanned::Spanned, Field, FieldsNamed, GenericArgument, Generics, Path, PathArguments, Result,
-    Type,
-};
-
-use crate::{
-    attr::{Attr, ContainerAttr, FieldAttr, Inflection, Optional, StructAttr},
-    deps::Dependencies,
-    schem::Schema,
-    utils::{raw_name_to_ts_field, to_ts_ident},
-    DerivedTS,
-};
-
-pub(crate) fn named(
-    attr: &StructAttr,
-    name: &str,
-    fields: &FieldsNamed,
-    generics: &Generics,
-) -> Result<DerivedTS> {
-    let crate_rename = attr.crate_rename();
-
-    let mut formatted_fields = Vec::new();
-    let mut flattened_fields = Vec::new();
-    let mut dependencies = Dependencies::new(crate_rename.clone());
-    let mut schema = Schema::new(name.to_string(), crate::schem::SchemaType::Struct);
-    for generic in generics.params.clone().into_iter() {
-        match generic {
-            syn::GenericParam::Type(ty) => {
-                schema.add_generic(ty.ident.clone());
-            }
-            _ => {}
-        }
-    }
-
-    if let Some(tag) = &attr.tag {
-        let formatted = format!("\"{}\": \"{}\",", tag, name);
-        formatted_fields.push(quote! {
-            #formatted.to_string()
-        });
-    }
-
-    for field in &fields.named {
-        format_field(
-            &crate_rename,
-            &mut formatted_fields,
-            &mut flattened_fields,
-            &mut dependencies,
-            &mut schema,
-            field,
-            &attr.rename_all,
-        )?;
-    }
-
-    let fields = quote!(<[String]>::join(&[#(#formatted_fields),*], " "));
-    let flattened = quote!(<[String]>::join(&[#(#flattened_fields),*], " & "));
-
-    let inline = match (formatted_fields.len(), flattened_fields.len()) {
-        (0, 0) => quote!("{  }".to_owned()),
-        (_, 0) => quote!(format!("{{ {} }}", #fields)),
-        (0, 1) => quote! {{
-            if #flattened.starts_with('(') && #flattened.ends_with(')') {
-                #flattened[1..#flattened.len() - 1].trim().to_owned()
-            } else {
-                #flattened.trim().to_owned()
-            }
-        }},
-        (0, _) => quote!(#flattened),
-        (_, _) => quote!(format!("{{ {} }} & {}", #fields, #flattened)),
-    };
-
-    let inline_flattened = match (formatted_fields.len(), flattened_fields.len()) {
-        (0, 0) => quote!("{  }".to_owned()),
-        (_, 0) => quote!(format!("{{ {} }}", #fields)),
-        (0, _) => quote!(#flattened),
-        (_, _) => quote!(format!("{{ {} }} & {}", #fields, #flattened)),
-    };
-
-    Ok(DerivedTS {
-        crate_rename,
-        // the `replace` combines `{ ... } & { ... }` into just one `{ ... }`. Not necessary, but it
-        // results in simpler type definitions.
-        inline: quote!(#inline.replace(" } & { ", " ")),
-        inline_flattened: Some(quote!(#inline_flattened.replace(" } & { ", " "))),
-        docs: attr.docs.clone(),
-        dependencies,
-        export: attr.export,
-        export_to: attr.export_to.clone(),
-        ts_name: name.to_owned(),
-        concrete: attr.concrete.clone(),
-        bound: attr.bound.clone(),
-        schema: Some(schema),
-    })
-}
-
-// build an expression which expands to a string, representing a single field of a struct.
-//
-// formatted_fields will contain all the fields that do not contain the flatten
-// attribute, in the format
-// key: type,
-//
-// flattened_fields will contain all the fields that contain the flatten attribute
-// in their respective formats, which for a named struct is the same as formatted_fields,
-// but for enums is
-// ({ /* variant data */ } | { /* variant data */ })
-fn format_field(
-    crate_rename: &Path,
-    formatted_fields: &mut Vec<TokenStream>,
-    flattened_fields: &mut Vec<TokenStream>,
-    dependencies: &mut Dependencies,
-    schema: &mut Schema,
-    field: &Field,
-    rename_all: &Option<Inflection>,
-) -> Result<()> {
-    let field_attr = FieldAttr::from_attrs(&field.attrs)?;
-
-    field_attr.assert_validity(field)?;
-
-    if field_attr.skip {
-        return Ok(());
-    }
-
-    let parsed_ty = field_attr.type_as(&field.ty);
-
-    let (ty, optional_annotation) = match field_attr.optional {
-        Optional {
-            optional: true,
-            nullable,
-        } => {
-            let inner_type = extract_option_argument(&parsed_ty)?; // inner type of the optional
-            match nullable {
-                true => (&parsed_ty, "?"),  // if it's nullable, we keep the original type
-                false => (inner_type, "?"), // if not, we use the Option's inner type
-            }
-        }
-        Optional {
-            optional: false, ..
-        } => (&parsed_ty, ""),
-    };
-
-    if field_attr.flatten {
-        flattened_fields.push(quote!(<#ty as #crate_rename::TS>::inline_flattened()));
-        dependencies.append_from(ty);
-        return Ok(());
-    }
-
-    let mut include_in_def = false;
-    let formatted_ty = field_attr
-        .type_override
-        .map(|t| quote!(#t))
-        .unwrap_or_else(|| {
-            if field_attr.inline {
-                dependencies.append_from(ty);
-                include_in_def = true;
-                quote!(<#ty as #crate_rename::TS>::inline())
-            } else {
-                dependencies.push(ty);
-                include_in_def = true;
-                quote!(<#ty as #crate_rename::TS>::name())
-            }
-        });
-
-    let field_name = to_ts_ident(field.ident.as_ref().unwrap());
-    let name = match (field_attr.rename, rename_all) {
-        (Some(rn), _) => rn,
-        (None, Some(rn)) => rn.apply(&field_name),
-        (None, None) => field_name,
-    };
-    let valid_name = raw_name_to_ts_field(name);
-
-    // Start every doc string with a newline, because when other characters are in front, it is not "understood" by VSCode
-    let docs = match field_attr.docs.is_empty() {
-        true => "".to_string(),
-        false => format!("\n{}", &field_attr.docs),
-    };
-
-    schema.add_field(valid_name.clone(), &parsed_ty);
-
-    formatted_fields.push(quote! {
-        format!("{}{}{}: {},", #docs, #valid_name, #optional_annotation, #formatted_ty)
-    });
-
-    Ok(())
-}
-
-fn extract_option_argument(ty: &Type) -> Result<&Type> {
-    match ty {
-        Type::Path(type_path)
-            if type_path.qself.is_none()
-                && type_path.path.leading_colon.is_none()
-                && type_path.path.segments.len() == 1
-                && type_path.path.segments[0].ident == "Option" =>
-        {
-            let segment = &type_path.path.segments[0];
-            match &segment.arguments {
-                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
-                    match &args.args[0] {
-                        GenericArgument::Type(inner_ty) => Ok(inner_ty),
-                        other => syn_err!(other.span(); "`Option` argument must be a type"),
-                    }
-                }
-                other => {
-                    syn_err!(other.span(); "`Option` type must have a single generic argument")
-                }
-            }
-        }
-        other => syn_err!(other.span(); "`optional` can only be used on an Option<T> type"),
-    }
-}
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    spanned::Spanned, Field, FieldsNamed, GenericArgument, Generics, Path, PathArguments, Result,
+    Type,
+};
+
+use crate::{
+    attr::{Attr, ContainerAttr, FieldAttr, Inflection, Optional, StructAttr},
+    deps::Dependencies,
+    schem::Schema,
+    utils::{raw_name_to_ts_field, to_ts_ident},
+    DerivedTS, Object,
+};
+
+pub(crate) fn named(
+    attr: &StructAttr,
+    name: &str,
+    fields: &FieldsNamed,
+    generics: &Generics,
+) -> Result<DerivedTS> {
+    let crate_rename = attr.crate_rename();
+
+    let mut formatted_fields = Vec::new();
+    let mut flattened_fields = Vec::new();
+    let mut flattened_types = Vec::new();
+    let mut dependencies = Dependencies::new(crate_rename.clone());
+    let mut schema = Schema::new(name.to_string(), crate::schem::SchemaType::Struct);
+    for generic in generics.params.clone().into_iter() {
+        match generic {
+            syn::GenericParam::Type(ty) => {
+                schema.add_generic(ty.ident.clone());
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(tag) = &attr.tag {
+        let formatted = format!("\"{}\": \"{}\",", tag, name);
+        formatted_fields.push(quote! {
+            format!("{}{}", #crate_rename::RenderOptions::current().readonly_modifier(), #formatted)
+        });
+    }
+
+    for field in &fields.named {
+        format_field(
+            &crate_rename,
+            &mut formatted_fields,
+            &mut flattened_fields,
+            &mut flattened_types,
+            &mut dependencies,
+            &mut schema,
+            field,
+            &attr.rename_all,
+        )?;
+    }
+
+    let fields = quote!(<[String]>::join(&[#(#formatted_fields),*], " "));
+    let flattened = quote!(<[String]>::join(&[#(#flattened_fields),*], " & "));
+
+    let inline = match (formatted_fields.len(), flattened_fields.len()) {
+        (0, 0) => quote!("{  }".to_owned()),
+        (_, 0) => quote!(format!("{{ {} }}", #fields)),
+        (0, 1) => quote! {{
+            if #flattened.starts_with('(') && #flattened.ends_with(')') {
+                #flattened[1..#flattened.len() - 1].trim().to_owned()
+            } else {
+                #flattened.trim().to_owned()
+            }
+        }},
+        (0, _) => quote!(#flattened),
+        (_, _) => quote!(format!("{{ {} }} & {}", #fields, #flattened)),
+    };
+
+    // an interface extends the types of all flattened fields, which is only possible if they are
+    // object types themselves
+    let is_object = quote!(true #(&& <#flattened_types as #crate_rename::TS>::OBJECT)*);
+    // without `#[ts(interface)]`, the `interface` setting of the configuration decides
+    let interface = (attr.interface != Some(false)).then(|| {
+        let configured = attr.interface.is_none();
+        for ty in &flattened_types {
+            dependencies.push_extends(ty, configured);
+        }
+
+        let enabled = match configured {
+            true => quote!(#crate_rename::RenderOptions::current().interface),
+            false => quote!(true),
+        };
+        quote! {
+            if #enabled && #is_object {
+                let extends: &[String] = &[
+                    #(<#flattened_types as #crate_rename::TS>::name()),*
+                ];
+                let extends = match extends {
+                    [] => String::new(),
+                    extends => format!("extends {} ", extends.join(", ")),
+                };
+                Some(format!("{}{{ {} }}", extends, #fields))
+            } else {
+                None
+            }
+        }
+    });
+
+    let inline_flattened = match (formatted_fields.len(), flattened_fields.len()) {
+        (0, 0) => quote!("{  }".to_owned()),
+        (_, 0) => quote!(format!("{{ {} }}", #fields)),
+        (0, _) => quote!(#flattened),
+        (_, _) => quote!(format!("{{ {} }} & {}", #fields, #flattened)),
+    };
+
+    Ok(DerivedTS {
+        crate_rename,
+        // the `replace` combines `{ ... } & { ... }` into just one `{ ... }`. Not necessary, but it
+        // results in simpler type definitions.
+        inline: quote!(#inline.replace(" } & { ", " ")),
+        inline_flattened: Some(quote!(#inline_flattened.replace(" } & { ", " "))),
+        docs: attr.docs.clone(),
+        dependencies,
+        export: attr.export,
+        export_to: attr.export_to.clone(),
+        ts_name: name.to_owned(),
+        concrete: attr.concrete.clone(),
+        bound: attr.bound.clone(),
+        schema: Some(schema),
+        object: Some(Object {
+            is_object,
+            interface,
+        }),
+        brand: None,
+    })
+}
+
+// build an expression which expands to a string, representing a single field of a struct.
+//
+// formatted_fields will contain all the fields that do not contain the flatten
+// attribute, in the format
+// key: type,
+//
+// flattened_fields will contain all the fields that contain the flatten attribute
+// in their respective formats, which for a named struct is the same as formatted_fields,
+// but for enums is
+// ({ /* variant data */ } | { /* variant data */ })
+//
+// flattened_types will contain the types of all fields that contain the flatten attribute
+#[allow(clippy::too_many_arguments)]
+fn format_field(
+    crate_rename: &Path,
+    formatted_fields: &mut Vec<TokenStream>,
+    flattened_fields: &mut Vec<TokenStream>,
+    flattened_types: &mut Vec<Type>,
+    dependencies: &mut Dependencies,
+    schema: &mut Schema,
+    field: &Field,
+    rename_all: &Option<Inflection>,
+) -> Result<()> {
+    let field_attr = FieldAttr::from_attrs(&field.attrs)?;
+
+    field_attr.assert_validity(field)?;
+
+    if field_attr.skip {
+        return Ok(());
+    }
+
+    let parsed_ty = field_attr.type_as(&field.ty);
+
+    let (ty, optional_annotation) = match field_attr.optional {
+        Optional {
+            optional: true,
+            nullable,
+        } => {
+            let inner_type = extract_option_argument(&parsed_ty)?; // inner type of the optional
+            match nullable {
+                true => (&parsed_ty, "?"),  // if it's nullable, we keep the original type
+                false => (inner_type, "?"), // if not, we use the Option's inner type
+            }
+        }
+        Optional {
+            optional: false, ..
+        } => (&parsed_ty, ""),
+    };
+
+    if field_attr.flatten {
+        flattened_fields.push(quote!(<#ty as #crate_rename::TS>::inline_flattened()));
+        flattened_types.push(ty.clone());
+        dependencies.append_from(ty);
+        return Ok(());
+    }
+
+    let mut include_in_def = false;
+    let formatted_ty = field_attr
+        .type_override
+        .as_ref()
+        .map(|t| quote!(#t))
+        .unwrap_or_else(|| {
+            if field_attr.inline {
+                dependencies.append_from(ty);
+                include_in_def = true;
+                quote!(<#ty as #crate_rename::TS>::inline())
+            } else {
+                dependencies.push(ty);
+                include_in_def = true;
+                quote!(<#ty as #crate_rename::TS>::name())
+            }
+        });
+    let formatted_ty = field_attr.render_scope(crate_rename, formatted_ty);
+
+    let field_name = to_ts_ident(field.ident.as_ref().unwrap());
+    let name = match (field_attr.rename, rename_all) {
+        (Some(rn), _) => rn,
+        (None, Some(rn)) => rn.apply(&field_name),
+        (None, None) => field_name,
+    };
+    let valid_name = raw_name_to_ts_field(name);
+
+    // Start every doc string with a newline, because when other characters are in front, it is not "understood" by VSCode
+    let docs = match field_attr.docs.is_empty() {
+        true => "".to_string(),
+        false => format!("\n{}", &field_attr.docs),
+    };
+
+    schema.add_field(
+        valid_name.clone(),
+        &parsed_ty,
+        field_attr.typed_array,
+        field_attr.es_collections,
+    );
+
+    formatted_fields.push(quote! {
+        format!(
+            "{}{}{}{}: {},",
+            #docs,
+            #crate_rename::RenderOptions::current().readonly_modifier(),
+            #valid_name,
+            #optional_annotation,
+            #formatted_ty
+        )
+    });
+
+    Ok(())
+}
+
+fn extract_option_argument(ty: &Type) -> Result<&Type> {
+    match ty {
+        Type::Path(type_path)
+            if type_path.qself.is_none()
+                && type_path.path.leading_colon.is_none()
+                && type_path.path.segments.len() == 1
+                && type_path.path.segments[0].ident == "Option" =>
+        {
+            let segment = &type_path.path.segments[0];
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match &args.args[0] {
+                        GenericArgument::Type(inner_ty) => Ok(inner_ty),
+                        other => syn_err!(other.span(); "`Option` argument must be a type"),
+                    }
+                }
+                other => {
+                    syn_err!(other.span(); "`Option` type must have a single generic argument")
+                }
+            }
+        }
+        other => syn_err!(other.span(); "`optional` can only be used on an Option<T> type"),
+    }
+}
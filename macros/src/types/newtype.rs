@@ -35,13 +35,29 @@ pub(crate) fn newtype(attr: &StructAttr, name: &str, fields: &FieldsUnnamed) ->
         }
     };
 
-    schema.add_field("0".to_string(), &inner_ty);
+    schema.add_field(
+        "0".to_string(),
+        &inner_ty,
+        field_attr.typed_array,
+        field_attr.es_collections,
+    );
 
     let inline_def = match field_attr.type_override {
         Some(ref o) => quote!(#o.to_owned()),
         None if field_attr.inline => quote!(<#inner_ty as #crate_rename::TS>::inline()),
         None => quote!(<#inner_ty as #crate_rename::TS>::name()),
     };
+    let inline_def = field_attr.render_scope(&crate_rename, inline_def);
+
+    // a branded type is its inner type, intersected with a unique brand
+    let (inline_def, brand) = match attr.brand {
+        false => (inline_def, None),
+        true => {
+            let brand = format!("{{ readonly __brand: \"{}\" }}", name);
+            let branded = quote!(#crate_rename::branded(&#inline_def, #brand));
+            (branded, Some(inline_def))
+        }
+    };
 
     Ok(DerivedTS {
         crate_rename,
@@ -55,5 +71,7 @@ pub(crate) fn newtype(attr: &StructAttr, name: &str, fields: &FieldsUnnamed) ->
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: Some(schema),
+        object: None,
+        brand,
     })
 }
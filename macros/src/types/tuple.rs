@@ -21,14 +21,17 @@ pub(crate) fn tuple(attr: &StructAttr, name: &str, fields: &FieldsUnnamed) -> Re
         )?;
     }
 
+    let inline = quote! {
+        format!(
+            "{}[{}]",
+            #crate_rename::RenderOptions::current().readonly_modifier(),
+            [#(#formatted_fields),*].join(", ")
+        )
+    };
+
     Ok(DerivedTS {
         crate_rename,
-        inline: quote! {
-            format!(
-                "[{}]",
-                [#(#formatted_fields),*].join(", ")
-            )
-        },
+        inline,
         inline_flattened: None,
         docs: attr.docs.clone(),
         dependencies,
@@ -38,6 +41,8 @@ pub(crate) fn tuple(attr: &StructAttr, name: &str, fields: &FieldsUnnamed) -> Re
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
@@ -56,11 +61,12 @@ fn format_field(
 
     let ty = field_attr.type_as(&field.ty);
 
-    formatted_fields.push(match field_attr.type_override {
+    let formatted_ty = match field_attr.type_override {
         Some(ref o) => quote!(#o.to_owned()),
         None if field_attr.inline => quote!(<#ty as #crate_rename::TS>::inline()),
         None => quote!(<#ty as #crate_rename::TS>::name()),
-    });
+    };
+    formatted_fields.push(field_attr.render_scope(crate_rename, formatted_ty));
 
     match (field_attr.inline, field_attr.type_override) {
         (_, Some(_)) => (),
@@ -22,6 +22,8 @@ pub(crate) fn type_as_struct(attr: &StructAttr, name: &str, type_as: &Type) -> R
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
@@ -40,5 +42,7 @@ pub(crate) fn type_as_enum(attr: &EnumAttr, name: &str, type_as: &Type) -> Resul
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
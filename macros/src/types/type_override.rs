@@ -26,6 +26,8 @@ pub(crate) fn type_override_struct(
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
@@ -48,5 +50,7 @@ pub(crate) fn type_override_enum(
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
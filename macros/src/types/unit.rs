@@ -23,6 +23,8 @@ pub(crate) fn empty_object(attr: &StructAttr, name: &str) -> Result<DerivedTS> {
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
@@ -42,6 +44,8 @@ pub(crate) fn empty_array(attr: &StructAttr, name: &str) -> Result<DerivedTS> {
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
@@ -61,6 +65,8 @@ pub(crate) fn null(attr: &StructAttr, name: &str) -> Result<DerivedTS> {
         concrete: attr.concrete.clone(),
         bound: attr.bound.clone(),
         schema: None,
+        object: None,
+        brand: None,
     })
 }
 
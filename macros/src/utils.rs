@@ -96,6 +96,83 @@ pub fn raw_name_to_ts_field(value: String) -> String {
     }
 }
 
+/// Returns the JavaScript typed array which can hold a value of the given type, if the type is a
+/// sequence of numeric primitives like `Vec<u8>`, `[f32; N]` or `bytes::Bytes`.
+/// `Option`s are looked through, so `Option<Vec<u8>>` results in `Uint8Array` as well.
+pub fn typed_array(ty: &Type) -> Option<&'static str> {
+    use syn::{GenericArgument as G, PathArguments as P, TypeArray, TypeReference, TypeSlice};
+
+    fn element(ty: &Type) -> Option<&'static str> {
+        let Type::Path(path) = ty else {
+            return None;
+        };
+        Some(match &*path.path.get_ident()?.to_string() {
+            "u8" => "Uint8Array",
+            "i8" => "Int8Array",
+            "u16" => "Uint16Array",
+            "i16" => "Int16Array",
+            "u32" => "Uint32Array",
+            "i32" => "Int32Array",
+            "f32" => "Float32Array",
+            "f64" => "Float64Array",
+            "u64" => "BigUint64Array",
+            "i64" => "BigInt64Array",
+            _ => return None,
+        })
+    }
+
+    match ty {
+        Type::Array(TypeArray { elem, .. }) | Type::Slice(TypeSlice { elem, .. }) => element(elem),
+        Type::Reference(TypeReference { elem, .. }) => typed_array(elem),
+        Type::Path(path) => {
+            let last = path.path.segments.last()?;
+            let first_arg = match &last.arguments {
+                P::AngleBracketed(args) => match args.args.first() {
+                    Some(G::Type(ty)) => Some(ty),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match (&*last.ident.to_string(), first_arg) {
+                ("Bytes" | "BytesMut", None) => Some("Uint8Array"),
+                ("Vec", Some(ty)) => element(ty),
+                ("Box" | "Option", Some(ty)) => typed_array(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the given type contains a map or a set, like `HashMap<K, V>` or `BTreeSet<T>`,
+/// which can be represented as an ES `Map` or `Set`.
+pub fn contains_collection(ty: &Type) -> bool {
+    use syn::{GenericArgument as G, PathArguments as P, TypeArray, TypeReference, TypeSlice};
+
+    match ty {
+        Type::Array(TypeArray { elem, .. })
+        | Type::Slice(TypeSlice { elem, .. })
+        | Type::Reference(TypeReference { elem, .. }) => contains_collection(elem),
+        Type::Tuple(tuple) => tuple.elems.iter().any(contains_collection),
+        Type::Path(path) => path.path.segments.iter().any(|segment| {
+            let is_collection = matches!(
+                &*segment.ident.to_string(),
+                "HashMap" | "BTreeMap" | "IndexMap" | "HashSet" | "BTreeSet" | "IndexSet"
+            );
+            let arguments = match &segment.arguments {
+                P::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .any(|arg| matches!(arg, G::Type(ty) if contains_collection(ty))),
+                _ => false,
+            };
+            is_collection || arguments
+        }),
+        _ => false,
+    }
+}
+
 /// Parse all `#[ts(..)]` attributes from the given slice.
 pub(crate) fn parse_attrs<'a, A>(attrs: &'a [Attribute]) -> Result<A>
 where
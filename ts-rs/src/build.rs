@@ -0,0 +1,313 @@
+//! Exporting bindings from a build script, so they are regenerated on every `cargo build`.
+//!
+//! A build script can't access the types of its own crate, so the types are exported from
+//! somewhere else, either
+//! - from the build script of a small helper crate, which depends on the crate declaring the
+//!   types as build-dependency, see `Export::registered`, or
+//! - by a binary of the crate itself, which is built with an additional feature enabling the
+//!   `registry` feature of ts-rs, see [`Export::binary`]. The build script itself doesn't
+//!   require the `registry` feature.
+//!
+//! In both cases, the types have to be annotated with `#[ts(export)]`, so that they are
+//! registered, see `ts_rs::export_registered`.
+//!
+//! Exporting prints `cargo:rerun-if-changed` directives for the sources of the crate, the files
+//! configuring ts-rs and the environment variables it reads, so the build script only runs again
+//! if the bindings may have changed. Without them, cargo would rerun it whenever a file within
+//! the package changes, including the bindings themselves if they are exported into it.
+//! Only configuration files which exist are watched, so after creating a new `ts-rs.toml`, the
+//! build script reruns once `Cargo.toml` or the sources change.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[cfg(feature = "registry")]
+use crate::Directory;
+use crate::{config::CONFIG_FILE, Config, ExportError, ExportReport};
+
+/// Set while the bindings are exported using [`Export::binary`], so that the build script of the
+/// crate doesn't try to export them again when the binary is built.
+const NESTED: &str = "TS_RS_BUILD_NESTED";
+
+/// Environment variables which change the exported bindings, besides the ones overriding the
+/// configuration
+const ENV: [&str; 2] = ["TS_RS_EXPORT_DIR", "TS_RS_CHECK"];
+
+/// Exports bindings from a build script.
+///
+/// ```ignore
+/// // build.rs of a helper crate, with `my-api` as build-dependency
+/// use my_api as _;
+///
+/// fn main() {
+///     ts_rs::build::Export::registered()
+///         .out_dir("../frontend/src/bindings")
+///         .run()
+///         .expect("could not export bindings");
+/// }
+/// ```
+pub struct Export {
+    source: Source,
+    package: Option<String>,
+    features: Vec<String>,
+    out_dir: Option<PathBuf>,
+    watch: Vec<PathBuf>,
+}
+
+/// Where the registered types are exported from
+enum Source {
+    /// The build script itself
+    #[cfg(feature = "registry")]
+    Registered,
+    /// A binary with the given name
+    Binary(String),
+}
+
+impl Export {
+    /// Exports all types annotated with `#[ts(export)]` which are linked into the build script.
+    ///
+    /// This requires a helper crate, which has the crate declaring the types as build-dependency
+    /// and references it within its build script, e.g using `use my_api as _;`. Otherwise, the
+    /// crate is not linked, and no types are registered.
+    /// Since cargo reruns the build script whenever a build-dependency changes, the bindings are
+    /// always up to date.
+    #[cfg(feature = "registry")]
+    pub fn registered() -> Self {
+        Self {
+            source: Source::Registered,
+            package: None,
+            features: vec![],
+            out_dir: None,
+            watch: vec![],
+        }
+    }
+
+    /// Builds and runs the binary `name` of the crate being built, which exports the registered
+    /// types, e.g
+    /// ```ignore
+    /// // src/bin/export-bindings.rs, with `required-features = ["ts-export"]`
+    /// use my_api as _;
+    ///
+    /// fn main() {
+    ///     ts_rs::build::export_registered().expect("could not export bindings");
+    /// }
+    /// ```
+    /// Within `Cargo.toml`, the feature `ts-export = ["ts-rs/registry"]` enables the registry
+    /// only for the binary, which is enabled using [`Export::features`].
+    ///
+    /// The binary is built within a separate target directory inside of `OUT_DIR`, since the
+    /// target directory of the build is locked while the build script runs.
+    /// The build script reruns if a file within `src` or the manifest of the crate changes.
+    pub fn binary(name: impl Into<String>) -> Self {
+        Self {
+            source: Source::Binary(name.into()),
+            package: None,
+            features: vec![],
+            out_dir: None,
+            // the manifest is watched along with the configuration
+            watch: vec![PathBuf::from("src")],
+        }
+    }
+
+    /// Builds the binary of `package` instead of the crate being built.
+    ///
+    /// The sources of the package are not watched, use [`Export::watch`] to rerun the build
+    /// script if they change.
+    pub fn package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    /// Enables `features` when building the binary, e.g the one enabling the registry.
+    pub fn features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Exports the bindings into `out_dir`, e.g `concat!(env!("OUT_DIR"), "/bindings")` or the
+    /// source directory of the frontend.
+    ///
+    /// Defaults to the export directory of the crate being built, see [`Config::export_dir`].
+    /// Relative paths are resolved against the directory of the crate.
+    pub fn out_dir(mut self, out_dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(out_dir.as_ref().to_owned());
+        self
+    }
+
+    /// Reruns the build script if the file or directory at `path` changes.
+    pub fn watch(mut self, path: impl AsRef<Path>) -> Self {
+        self.watch.push(path.as_ref().to_owned());
+        self
+    }
+
+    /// Exports the bindings, printing the `cargo:rerun-if-changed` directives for them.
+    ///
+    /// Returns the report of the export, or `None` if the bindings were exported by a binary,
+    /// or if the binary is being built by the build script.
+    pub fn run(self) -> Result<Option<ExportReport>, ExportError> {
+        // the binary is being built by the build script of its own crate
+        if std::env::var_os(NESTED).is_some() {
+            return Ok(None);
+        }
+
+        let crate_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .ok_or(ExportError::ManifestDirNotSet)?;
+        for directive in self.rerun_if_changed(&crate_dir) {
+            println!("{}", directive);
+        }
+
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => crate_dir.join(out_dir),
+            None => Config::current()?.export_dir(),
+        };
+
+        match &self.source {
+            #[cfg(feature = "registry")]
+            Source::Registered => {
+                let report = crate::export_registered_with_report(&mut Directory::new(out_dir))?;
+                Ok(Some(report))
+            }
+            Source::Binary(name) => {
+                self.run_binary(&crate_dir, &out_dir, name)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the `cargo:rerun-if-changed` and `cargo:rerun-if-env-changed` directives
+    fn rerun_if_changed(&self, crate_dir: &Path) -> Vec<String> {
+        // the configuration is read from the crate and all of its ancestors
+        let config = crate_dir
+            .ancestors()
+            .flat_map(|dir| [dir.join("Cargo.toml"), dir.join(CONFIG_FILE)])
+            .filter(|file| file.is_file());
+
+        // paths which don't exist would make cargo rerun the build script every time
+        let watched = self.watch.iter().map(|path| crate_dir.join(path));
+        let paths = config
+            .chain(watched)
+            .filter(|path| path.exists())
+            .map(|path| format!("cargo:rerun-if-changed={}", path.display()));
+
+        let config_env = crate::config::ENV.map(|(var, _)| var);
+        let vars = ENV
+            .into_iter()
+            .chain(config_env)
+            .map(|var| format!("cargo:rerun-if-env-changed={}", var));
+        paths.chain(vars).collect()
+    }
+
+    /// Builds and runs the binary `name`, which exports the bindings into `out_dir`
+    fn run_binary(&self, crate_dir: &Path, out_dir: &Path, name: &str) -> Result<(), ExportError> {
+        let target_dir = match std::env::var_os("OUT_DIR") {
+            Some(dir) => PathBuf::from(dir).join("ts-rs-target"),
+            None => crate_dir.join("target").join("ts-rs"),
+        };
+        let package = self
+            .package
+            .clone()
+            .or_else(|| std::env::var("CARGO_PKG_NAME").ok());
+
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+        let mut command = Command::new(cargo);
+        command
+            .current_dir(crate_dir)
+            .args(["run", "--quiet", "--bin", name])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .env("TS_RS_EXPORT_DIR", out_dir)
+            .env(NESTED, "1");
+        if let Some(package) = &package {
+            command.args(["--package", package]);
+        }
+        if !self.features.is_empty() {
+            command.args(["--features", &self.features.join(",")]);
+        }
+
+        let output = command.output()?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        Err(ExportError::Command {
+            command: format!("{:?}", command),
+            output: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Exports all registered types into the export directory of the crate, see
+/// [`Config::export_dir`].
+///
+/// This is meant to be called from the binary run by [`Export::binary`], which sets
+/// `TS_RS_EXPORT_DIR` to the directory passed to [`Export::out_dir`].
+#[cfg(feature = "registry")]
+pub fn export_registered() -> Result<ExportReport, ExportError> {
+    let out_dir = Config::current()?.export_dir();
+    crate::export_registered_with_report(&mut Directory::new(out_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn rerun_if_changed() {
+        let dir = std::env::temp_dir().join(format!("ts-rs-build-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("crate/src")).unwrap();
+        for file in ["Cargo.toml", "ts-rs.toml", "crate/Cargo.toml"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let export = Export::binary("export-bindings").watch("missing");
+        let directives = export.rerun_if_changed(&dir.join("crate"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        // directories above the temporary one may contain manifests as well
+        let paths = directives
+            .iter()
+            .filter_map(|d| d.strip_prefix("cargo:rerun-if-changed="))
+            .filter(|path| path.starts_with(dir.to_str().unwrap()))
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                dir.join("crate/Cargo.toml"),
+                dir.join("Cargo.toml"),
+                dir.join("ts-rs.toml"),
+                dir.join("crate/src"),
+            ]
+        );
+
+        let vars = directives
+            .iter()
+            .filter_map(|d| d.strip_prefix("cargo:rerun-if-env-changed="))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vars,
+            [
+                "TS_RS_EXPORT_DIR",
+                "TS_RS_CHECK",
+                "TS_RS_IMPORT_EXTENSION",
+                "TS_RS_FORMAT"
+            ]
+        );
+    }
+
+    #[test]
+    fn nested() {
+        // building a binary which doesn't exist would fail
+        std::env::set_var(NESTED, "1");
+        let result = Export::binary("missing").run();
+        std::env::remove_var(NESTED);
+
+        assert!(matches!(result, Ok(None)));
+    }
+}
@@ -0,0 +1,605 @@
+//! Project-level configuration, read from `ts-rs.toml` files and the `[package.metadata.ts-rs]`
+//! and `[workspace.metadata.ts-rs]` tables of `Cargo.toml` manifests.
+//!
+//! Unlike cargo features, which are unified across a workspace, every crate can be configured
+//! individually: configurations closer to the crate override the ones further up, e.g the
+//! `ts-rs.toml` at the root of the workspace.
+//!
+//! The bindings of a type are generated using the configuration of the crate declaring it, no
+//! matter which crate exports them. Only the export directory, and the `index.ts` of the
+//! `barrel` feature, follow the crate being exported, since every type exported at once is
+//! written into the same directory.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use lazy_static::lazy_static;
+
+use crate::{export::NOTE, ExportError, TS};
+
+/// Name of the configuration file
+pub(crate) const CONFIG_FILE: &str = "ts-rs.toml";
+
+/// Environment variables overriding a setting of every crate, e.g set by `cargo ts-rs`
+pub(crate) const ENV: [(&str, &str); 2] = [
+    ("TS_RS_IMPORT_EXTENSION", "import_extension"),
+    ("TS_RS_FORMAT", "format"),
+];
+
+lazy_static! {
+    /// The configuration of the crate being exported
+    static ref CURRENT: Result<Config, LoadError> = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Config::load_from(Path::new(&dir)),
+        // without knowing the crate, there's no crate or workspace to resolve paths against
+        None => match Config::load_from(&std::env::current_dir().unwrap_or_default()) {
+            Ok(config) if config.base.is_some() => Err(LoadError::ManifestDirNotSet),
+            result => result,
+        },
+    };
+
+    /// The configurations of other crates declaring exported types, by their directory
+    static ref CRATES: Mutex<HashMap<&'static str, &'static Result<Config, LoadError>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// An error which occurred while loading the configuration
+#[derive(Clone, Debug)]
+enum LoadError {
+    Invalid(PathBuf, String),
+    ManifestDirNotSet,
+}
+
+impl From<LoadError> for ExportError {
+    fn from(error: LoadError) -> Self {
+        match error {
+            LoadError::Invalid(path, message) => ExportError::Config { path, message },
+            LoadError::ManifestDirNotSet => ExportError::ManifestDirNotSet,
+        }
+    }
+}
+
+/// Settings controlling how bindings are exported.
+///
+/// ```toml
+/// # ts-rs.toml
+/// base = "workspace"
+/// out_dir = "frontend/src/bindings"
+/// import_extension = "js"
+/// output = "ts"
+/// file_names = "kebab-case"
+/// bigint = "number"
+/// typed_arrays = true
+/// readonly = true
+/// interface = true
+/// es_collections = true
+/// header = """
+/// /* eslint-disable */
+/// // Generated by ts-rs {version}, do not edit. Hash: {hash}"""
+///
+/// [format]
+/// indent_width = 2
+/// quotes = "single"
+/// semicolons = false
+/// line_width = 100
+/// ```
+/// The same keys can be put into the `[package.metadata.ts-rs]` table of a crate's `Cargo.toml`,
+/// or into `[workspace.metadata.ts-rs]`.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Directory the bindings are exported to, relative to the file configuring it.
+    /// `TS_RS_EXPORT_DIR` takes precedence over this setting.
+    pub out_dir: Option<PathBuf>,
+    /// Directory a relative `TS_RS_EXPORT_DIR` and the default `./bindings` are resolved against,
+    /// instead of the working directory, which is the directory of the crate when running
+    /// `cargo test`.
+    ///
+    /// Set using `base = "workspace"` for the root of the workspace, `base = "crate"` for the
+    /// directory of the crate, or a path relative to the file configuring it.
+    /// Anchoring the bindings of all crates of a workspace in the same directory makes imports
+    /// between them work.
+    pub base: Option<PathBuf>,
+    /// Extension of the paths in `import` statements.
+    /// Defaults to `.js` if the `import-esm` feature is enabled.
+    pub import_extension: ImportExtension,
+    /// Whether bindings are exported as TypeScript, or as JavaScript with type declarations
+    pub output: Output,
+    /// Case of the names of the files types are exported to, e.g `user-profile.ts` instead of
+    /// `UserProfile.ts`.
+    ///
+    /// Files named using `#[ts(export_to = "...")]` keep their name, while the files within a
+    /// directory given by `#[ts(export_to = "dir/")]` follow this setting as well.
+    /// Defaults to the name of the type as is.
+    pub file_names: FileNameCase,
+    /// How the bindings are formatted, or `None` if they are not.
+    ///
+    /// Set using `format = true`, or a `[format]` table overriding some of the settings.
+    /// Defaults to formatting using dprint if the `format` feature is enabled.
+    pub format: Option<Format>,
+    /// How 64 and 128 bit integers are represented
+    pub bigint: BigIntStyle,
+    /// Whether sequences of numeric primitives, like `Vec<u8>` or `[f32; N]`, are represented as
+    /// JavaScript typed arrays, e.g `Uint8Array`, instead of `Array<number>`.
+    /// A single field can opt in using `#[ts(typed_array)]`.
+    pub typed_arrays: bool,
+    /// Whether types are immutable, with `readonly` properties and `ReadonlyArray<T>` instead of
+    /// `Array<T>`. A single type can opt in or out using `#[ts(readonly)]` and
+    /// `#[ts(readonly = false)]`.
+    pub readonly: bool,
+    /// Whether structs with named fields are declared as `interface`s instead of type aliases.
+    /// A single struct can opt in or out using `#[ts(interface)]` and `#[ts(interface = false)]`.
+    pub interface: bool,
+    /// Whether maps are represented as `Map<K, V>` and sets as `Set<T>` instead of objects and
+    /// arrays. A single field can opt in using `#[ts(es_collections)]`.
+    pub es_collections: bool,
+    /// Header at the top of every generated file, e.g a license or `/* eslint-disable */`.
+    ///
+    /// `{version}` is replaced by the version of ts-rs, `{hash}` by a hash of the rest of the
+    /// file and `{file}` by its path within the export directory.
+    /// Defaults to a comment noting that the file was generated by ts-rs. Files without a header
+    /// are not recognized as generated, so they are never pruned.
+    pub header: String,
+}
+
+/// The extension of paths within `import` statements
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImportExtension {
+    /// `import type { User } from "./User"`
+    None,
+    /// `import type { User } from "./User.js"`, as required by ES modules
+    Js,
+    /// `import type { User } from "./User.ts"`, for projects using `allowImportingTsExtensions`
+    Ts,
+}
+
+/// The kind of files bindings are exported to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// `User.ts`, containing both the types and values like schemas
+    TypeScript,
+    /// `User.d.ts`, containing the types, and `User.js`, containing the values, which can be used
+    /// without a TypeScript build step. Imports always use the `.js` extension.
+    JavaScript,
+}
+
+/// Case of the names of generated files
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileNameCase {
+    /// The name of the type as is
+    None,
+    /// `UserProfile.ts`
+    Pascal,
+    /// `userProfile.ts`
+    Camel,
+    /// `user-profile.ts`
+    Kebab,
+    /// `user_profile.ts`
+    Snake,
+}
+
+/// Settings of the formatter
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Format {
+    /// Defaults to dprint if the `format` feature is enabled, and to the built-in pretty-printer
+    /// otherwise
+    pub formatter: Formatter,
+    /// Number of spaces per indentation level
+    pub indent_width: u8,
+    /// Width lines are broken at if possible
+    pub line_width: u32,
+    /// Quotes around string literals
+    pub quotes: Quotes,
+    /// Whether statements end with a semicolon
+    pub semicolons: bool,
+    /// A `dprint.json` configuring dprint, which is used instead of the settings above
+    pub dprint_config: Option<PathBuf>,
+}
+
+/// Formats the bindings
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Formatter {
+    /// [dprint](https://dprint.dev), which requires the `format` feature
+    Dprint,
+    /// A lightweight pretty-printer without any dependencies, which re-indents the bindings and
+    /// breaks long lines, but doesn't parse them
+    BuiltIn,
+}
+
+/// Quotes around string literals
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Quotes {
+    /// `"value"`
+    Double,
+    /// `'value'`
+    Single,
+}
+
+/// How integers which don't fit into a `number`, like `u64` or `i128`, are represented
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BigIntStyle {
+    /// `bigint`
+    BigInt,
+    /// `number`, which loses precision for values above `Number.MAX_SAFE_INTEGER`
+    Number,
+    /// `string`
+    String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            out_dir: None,
+            base: None,
+            import_extension: match cfg!(feature = "import-esm") {
+                true => ImportExtension::Js,
+                false => ImportExtension::None,
+            },
+            output: Output::TypeScript,
+            file_names: FileNameCase::None,
+            format: cfg!(feature = "format").then(Format::default),
+            bigint: BigIntStyle::BigInt,
+            typed_arrays: false,
+            readonly: false,
+            interface: false,
+            es_collections: false,
+            header: NOTE.to_owned(),
+        }
+    }
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            formatter: match cfg!(feature = "format") {
+                true => Formatter::Dprint,
+                false => Formatter::BuiltIn,
+            },
+            indent_width: 2,
+            line_width: 80,
+            quotes: Quotes::Double,
+            semicolons: true,
+            dprint_config: None,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the configuration of the crate being exported, which is located using
+    /// `CARGO_MANIFEST_DIR`.
+    pub fn current() -> Result<&'static Self, ExportError> {
+        CURRENT.as_ref().map_err(|e| e.clone().into())
+    }
+
+    /// Returns the configuration of the crate declaring `T`, which applies to its bindings.
+    /// Types which weren't derived, e.g primitives, use the one of the crate being exported.
+    pub(crate) fn of<T: TS + ?Sized>() -> Result<&'static Self, ExportError> {
+        Self::of_crate(T::crate_dir())
+    }
+
+    /// Returns the configuration of the crate in `crate_dir`, which is loaded once.
+    pub(crate) fn of_crate(crate_dir: Option<&'static str>) -> Result<&'static Self, ExportError> {
+        let current = std::env::var_os("CARGO_MANIFEST_DIR");
+        let crate_dir = match crate_dir {
+            Some(dir) if current.as_deref() != Some(dir.as_ref()) => dir,
+            _ => return Self::current(),
+        };
+
+        let mut crates = CRATES.lock().unwrap_or_else(|e| e.into_inner());
+        let config = crates
+            .entry(crate_dir)
+            // leaked once for every crate, like the configuration of the current one
+            .or_insert_with(|| Box::leak(Box::new(Self::load_from(Path::new(crate_dir)))));
+        config.as_ref().map_err(|e| e.clone().into())
+    }
+
+    /// Loads the configuration of the crate in `dir`.
+    ///
+    /// Every `ts-rs.toml` and `Cargo.toml` within `dir` and its ancestors is read, with the files
+    /// closest to `dir` taking precedence. Within a directory, `ts-rs.toml` takes precedence over
+    /// `Cargo.toml`.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, ExportError> {
+        Ok(Self::load_from(dir.as_ref())?)
+    }
+
+    /// Returns the export directory configured by `TS_RS_EXPORT_DIR` or [`Config::out_dir`],
+    /// resolved against [`Config::base`], or `./bindings` if there is none.
+    pub fn export_dir(&self) -> PathBuf {
+        let dir = match std::env::var_os("TS_RS_EXPORT_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => match &self.out_dir {
+                Some(out_dir) => return out_dir.clone(),
+                None => PathBuf::from("./bindings"),
+            },
+        };
+        match &self.base {
+            Some(base) => base.join(dir),
+            None => dir,
+        }
+    }
+
+    /// Returns true if `contents` start with the configured header, or with the default one,
+    /// meaning that the file was generated by ts-rs.
+    pub fn is_generated(&self, contents: &str) -> bool {
+        crate::export::header::strip(self, contents).is_some()
+    }
+
+    fn load_from(crate_dir: &Path) -> Result<Self, LoadError> {
+        let crate_dir = std::env::current_dir()
+            .map(|cwd| cwd.join(crate_dir))
+            .unwrap_or_else(|_| crate_dir.to_owned());
+
+        // the root of the workspace is the closest directory containing a workspace manifest
+        let mut workspace = crate_dir.clone();
+        let mut base = None;
+        let mut config = Self::default();
+        for dir in crate_dir.ancestors().collect::<Vec<_>>().into_iter().rev() {
+            let manifest = dir.join("Cargo.toml");
+            if let Some(table) = read(&manifest)? {
+                if table.contains_key("workspace") {
+                    workspace = dir.to_owned();
+                }
+                for key in ["workspace", "package"] {
+                    let metadata = table
+                        .get(key)
+                        .and_then(|t| t.get("metadata"))
+                        .and_then(|t| t.get("ts-rs"));
+                    if let Some(metadata) = metadata {
+                        config
+                            .apply(dir, metadata, &mut base)
+                            .map_err(|e| LoadError::Invalid(manifest.clone(), e))?;
+                    }
+                }
+            }
+
+            let file = dir.join(CONFIG_FILE);
+            if let Some(table) = read(&file)? {
+                config
+                    .apply(dir, &toml::Value::Table(table), &mut base)
+                    .map_err(|e| LoadError::Invalid(file, e))?;
+            }
+        }
+
+        // the environment takes precedence over all files
+        for (var, key) in ENV {
+            let value = match std::env::var(var) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let value = match value.as_str() {
+                "true" | "1" => toml::Value::Boolean(true),
+                "false" | "0" => toml::Value::Boolean(false),
+                _ => toml::Value::String(value),
+            };
+            let table = toml::value::Table::from_iter([(key.to_owned(), value)]);
+            config
+                .apply(&crate_dir, &toml::Value::Table(table), &mut base)
+                .map_err(|e| LoadError::Invalid(PathBuf::from(var), e))?;
+        }
+
+        config.base = base.map(|base| match base {
+            Base::Workspace => workspace,
+            Base::Crate => crate_dir,
+            Base::Dir(dir) => dir,
+        });
+        Ok(config)
+    }
+
+    /// Overrides the settings present in `value`, which was read from a file within `dir`.
+    /// Since the root of the workspace is not known yet, the base is set separately.
+    fn apply(
+        &mut self,
+        dir: &Path,
+        value: &toml::Value,
+        base: &mut Option<Base>,
+    ) -> Result<(), String> {
+        let table = value
+            .as_table()
+            .ok_or("the configuration must be a table")?;
+
+        for (key, value) in table {
+            let invalid = || format!("invalid value for `{}`: {}", key, value);
+            match key.as_str() {
+                "out_dir" => {
+                    let out_dir = value.as_str().ok_or_else(invalid)?;
+                    self.out_dir = Some(dir.join(out_dir));
+                }
+                "import_extension" => {
+                    self.import_extension = match value.as_str() {
+                        Some("none") => ImportExtension::None,
+                        Some("js") => ImportExtension::Js,
+                        Some("ts") => ImportExtension::Ts,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "output" => {
+                    self.output = match value.as_str() {
+                        Some("ts") => Output::TypeScript,
+                        Some("js") => Output::JavaScript,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "file_names" => {
+                    self.file_names = match value.as_str() {
+                        Some("none") => FileNameCase::None,
+                        Some("PascalCase") => FileNameCase::Pascal,
+                        Some("camelCase") => FileNameCase::Camel,
+                        Some("kebab-case") => FileNameCase::Kebab,
+                        Some("snake_case") => FileNameCase::Snake,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "base" => {
+                    *base = match value.as_str().ok_or_else(invalid)? {
+                        "workspace" => Some(Base::Workspace),
+                        "crate" => Some(Base::Crate),
+                        path => Some(Base::Dir(dir.join(path))),
+                    }
+                }
+                "format" => match value {
+                    toml::Value::Boolean(format) => {
+                        self.format = format.then(|| self.format.take().unwrap_or_default());
+                    }
+                    toml::Value::Table(table) => {
+                        let mut format = self.format.take().unwrap_or_default();
+                        format.apply(dir, table)?;
+                        self.format = Some(format);
+                    }
+                    _ => return Err(invalid()),
+                },
+                "bigint" => {
+                    self.bigint = match value.as_str() {
+                        Some("bigint") => BigIntStyle::BigInt,
+                        Some("number") => BigIntStyle::Number,
+                        Some("string") => BigIntStyle::String,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "typed_arrays" => self.typed_arrays = value.as_bool().ok_or_else(invalid)?,
+                "readonly" => self.readonly = value.as_bool().ok_or_else(invalid)?,
+                "interface" => self.interface = value.as_bool().ok_or_else(invalid)?,
+                "es_collections" => self.es_collections = value.as_bool().ok_or_else(invalid)?,
+                "header" => {
+                    let header = value.as_str().ok_or_else(invalid)?;
+                    self.header = header.trim_end_matches('\n').to_owned();
+                }
+                _ => return Err(format!("unknown setting `{}`", key)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FileNameCase {
+    /// Converts the name of a type into the name of its file, without extension.
+    ///
+    /// Words are split at underscores, hyphens and changes of case, treating acronyms as one
+    /// word, so `HTTPServer` becomes `http-server` in kebab-case.
+    pub fn apply(self, name: &str) -> String {
+        let words = words(name);
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        };
+
+        match self {
+            FileNameCase::None => name.to_owned(),
+            FileNameCase::Pascal => words.iter().map(capitalize).collect(),
+            FileNameCase::Camel => match words.split_first() {
+                Some((first, rest)) => {
+                    let rest = rest.iter().map(capitalize).collect::<String>();
+                    first.clone() + rest.as_str()
+                }
+                None => String::new(),
+            },
+            FileNameCase::Kebab => words.join("-"),
+            FileNameCase::Snake => words.join("_"),
+        }
+    }
+}
+
+/// Splits `name` into lowercase words
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        // a new word starts at an uppercase letter following a lowercase one, or at the last
+        // letter of an acronym if it's followed by a lowercase one, e.g the `S` of `HTTPServer`
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        let starts_word = c.is_uppercase()
+            && match previous {
+                Some(p) if p.is_uppercase() => next.map_or(false, |n| n.is_lowercase()),
+                Some(p) => p.is_alphanumeric(),
+                None => false,
+            };
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+impl Format {
+    /// Overrides the settings present in `table`, which was read from a file within `dir`
+    fn apply(&mut self, dir: &Path, table: &toml::value::Table) -> Result<(), String> {
+        for (key, value) in table {
+            let invalid = || format!("invalid value for `format.{}`: {}", key, value);
+            match key.as_str() {
+                "formatter" => {
+                    self.formatter = match value.as_str() {
+                        Some("dprint") if cfg!(feature = "format") => Formatter::Dprint,
+                        Some("dprint") => return Err("dprint requires the `format` feature".into()),
+                        Some("builtin") => Formatter::BuiltIn,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "indent_width" => {
+                    let width = value.as_integer().ok_or_else(invalid)?;
+                    self.indent_width = u8::try_from(width).map_err(|_| invalid())?;
+                }
+                "line_width" => {
+                    let width = value.as_integer().ok_or_else(invalid)?;
+                    self.line_width = u32::try_from(width).map_err(|_| invalid())?;
+                }
+                "quotes" => {
+                    self.quotes = match value.as_str() {
+                        Some("double") => Quotes::Double,
+                        Some("single") => Quotes::Single,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "semicolons" => self.semicolons = value.as_bool().ok_or_else(invalid)?,
+                "dprint_config" => {
+                    let path = value.as_str().ok_or_else(invalid)?;
+                    self.dprint_config = Some(dir.join(path));
+                }
+                _ => return Err(format!("unknown setting `format.{}`", key)),
+            }
+        }
+
+        if self.dprint_config.is_some() && self.formatter != Formatter::Dprint {
+            return Err("`format.dprint_config` requires dprint as formatter".into());
+        }
+        Ok(())
+    }
+}
+
+/// The directory relative export directories are resolved against, see [`Config::base`]
+enum Base {
+    Workspace,
+    Crate,
+    Dir(PathBuf),
+}
+
+/// Reads the TOML file at `path`, returning `None` if it doesn't exist
+fn read(path: &Path) -> Result<Option<toml::value::Table>, LoadError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(LoadError::Invalid(path.to_owned(), e.to_string())),
+    };
+    toml::from_str::<toml::value::Table>(&contents)
+        .map(Some)
+        .map_err(|e| LoadError::Invalid(path.to_owned(), e.to_string()))
+}
@@ -1,82 +1,140 @@
 use std::{
     any::TypeId,
     borrow::Cow,
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Write,
     fs::File,
-    io::{Seek, SeekFrom},
+    io::Write as _,
     path::{Component, Path, PathBuf},
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Instant,
 };
 
+#[cfg(feature = "tar-archive")]
+pub use archive::TarArchive;
+#[cfg(feature = "zip-archive")]
+pub use archive::ZipArchive;
+pub use bundle::Bundle;
 pub use error::ExportError;
 use lazy_static::lazy_static;
+pub use manifest::prune;
 use path::diff_paths;
+#[cfg(feature = "module-paths")]
+pub use path::module_output_path;
+pub use path::type_output_path;
 pub(crate) use recursive_export::export_all_into;
-
-use crate::{Dependency, TS};
-
+#[cfg(feature = "registry")]
+pub(crate) use recursive_export::export_recursive;
+pub use report::{ExportReport, FileReport, FileStatus};
+pub use sink::{Directory, ExportSink};
+
+use crate::{config::ImportExtension, render, Config, Dependency, Output, RenderOptions, TS};
+
+#[cfg(any(feature = "tar-archive", feature = "zip-archive"))]
+mod archive;
+mod barrel;
+mod bundle;
+pub(crate) mod check;
+mod diff;
+#[cfg(feature = "format")]
+mod dprint;
 mod error;
+pub(crate) mod header;
+mod manifest;
+mod merge;
 mod path;
+mod pretty;
+mod report;
+mod sink;
+
+/// Names declared within every file, together with the rust type declaring them
+type Declared = HashMap<PathBuf, HashMap<String, &'static str>>;
 
 lazy_static! {
-    static ref EXPORT_PATHS: Mutex<HashMap<PathBuf, HashSet<String>>> = Mutex::new(HashMap::new());
+    /// Types declared within files on disk, which are tracked for the whole process
+    static ref EXPORT_PATHS: Mutex<Declared> = Mutex::new(HashMap::new());
 }
 
-const NOTE: &str = "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n";
+/// State shared by all types exported into a sink at once
+#[derive(Default)]
+pub(crate) struct Session {
+    /// Types which were already exported
+    seen: HashSet<TypeId>,
+    /// Types declared within files which are not on disk
+    declared: Declared,
+    /// What happened so far
+    pub(crate) report: ExportReport,
+}
+
+/// The default header of generated files
+pub(crate) const NOTE: &str = "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.";
 
 mod recursive_export {
-    use std::{any::TypeId, collections::HashSet, path::Path};
+    use std::{any::TypeId, time::Instant};
 
-    use super::export_into;
-    use crate::{ExportError, TypeVisitor, TS};
+    use super::{export_into, ExportReport, ExportSink, Session};
+    use crate::{Config, ExportError, RenderOptions, TypeVisitor, TS};
 
     /// Exports `T` to the file specified by the `#[ts(export_to = ..)]` attribute within the given
-    /// base directory.  
+    /// sink.  
     /// Additionally, all dependencies of `T` will be exported as well.
     pub(crate) fn export_all_into<T: TS + ?Sized + 'static>(
-        out_dir: impl AsRef<Path>,
-    ) -> Result<(), ExportError> {
-        let mut seen = HashSet::new();
-        export_recursive::<T>(&mut seen, out_dir)
+        sink: &mut (impl ExportSink + ?Sized),
+    ) -> Result<ExportReport, ExportError> {
+        let started = Instant::now();
+        let mut session = Session::default();
+        export_recursive::<T>(&mut session, sink)?;
+
+        session.report.duration = started.elapsed();
+        Ok(session.report)
     }
 
-    struct Visit<'a> {
-        seen: &'a mut HashSet<TypeId>,
-        out_dir: &'a Path,
+    struct Visit<'a, S: ExportSink + ?Sized> {
+        session: &'a mut Session,
+        sink: &'a mut S,
         error: Option<ExportError>,
     }
 
-    impl<'a> TypeVisitor for Visit<'a> {
+    impl<'a, S: ExportSink + ?Sized> TypeVisitor for Visit<'a, S> {
         fn visit<T: TS + 'static + ?Sized>(&mut self) {
             // if an error occurred previously, or the type cannot be exported (it's a primitive),
             // we return
-            if self.error.is_some() || T::output_path().is_none() {
+            if self.error.is_some() {
+                return;
+            }
+            if T::output_path().is_none() {
+                let skipped = &mut self.session.report.skipped;
+                skipped.insert(std::any::type_name::<T>());
                 return;
             }
 
-            self.error = export_recursive::<T>(self.seen, self.out_dir).err();
+            self.error = export_recursive::<T>(self.session, self.sink).err();
         }
     }
 
     // exports T, then recursively calls itself with all of its dependencies
-    fn export_recursive<T: TS + ?Sized + 'static>(
-        seen: &mut HashSet<TypeId>,
-        out_dir: impl AsRef<Path>,
+    pub(crate) fn export_recursive<T: TS + ?Sized + 'static>(
+        session: &mut Session,
+        sink: &mut (impl ExportSink + ?Sized),
     ) -> Result<(), ExportError> {
-        if !seen.insert(TypeId::of::<T>()) {
+        if !session.seen.insert(TypeId::of::<T>()) {
             return Ok(());
         }
-        let out_dir = out_dir.as_ref();
 
-        export_into::<T>(out_dir)?;
+        export_into::<T>(session, sink)?;
 
         let mut visitor = Visit {
-            seen,
-            out_dir,
+            session,
+            sink,
             error: None,
         };
-        T::visit_dependencies(&mut visitor);
+        // which types `T` depends on is decided by the configuration of the crate declaring it,
+        // e.g. whether it is declared as an interface extending others
+        let options = RenderOptions::current().configure(Config::of::<T>()?);
+        options.scope(|| T::visit_dependencies(&mut visitor));
 
         if let Some(e) = visitor.error {
             Err(e)
@@ -88,191 +146,220 @@ mod recursive_export {
 
 /// Export `T` to the file specified by the `#[ts(export_to = ..)]` attribute
 pub(crate) fn export_into<T: TS + ?Sized + 'static>(
-    out_dir: impl AsRef<Path>,
+    session: &mut Session,
+    sink: &mut (impl ExportSink + ?Sized),
 ) -> Result<(), ExportError> {
     let path = T::output_path()
         .ok_or_else(std::any::type_name::<T>)
         .map_err(ExportError::CannotBeExported)?;
-    let path = out_dir.as_ref().join(path);
 
-    export_to::<T, _>(path::absolute(path)?)
+    export_to::<T>(session, sink, path)
 }
 
 /// Export `T` to the file specified by the `path` argument.
-pub(crate) fn export_to<T: TS + ?Sized + 'static, P: AsRef<Path>>(
-    path: P,
+fn export_to<T: TS + ?Sized + 'static>(
+    session: &mut Session,
+    sink: &mut (impl ExportSink + ?Sized),
+    path: &Path,
 ) -> Result<(), ExportError> {
-    let path = path.as_ref().to_owned();
-    let type_name = T::ident();
-
-    #[allow(unused_mut)]
-    let mut buffer = export_to_string::<T>()?;
-
-    // format output
-    #[cfg(feature = "format")]
-    {
-        use dprint_plugin_typescript::{configuration::ConfigurationBuilder, format_text};
-
-        let fmt_cfg = ConfigurationBuilder::new().deno().build();
-        if let Some(formatted) = format_text(path.as_ref(), &buffer, &fmt_cfg)
-            .map_err(|e| ExportError::Formatting(e.to_string()))?
-        {
-            buffer = formatted;
+    let entry = barrel::Entry {
+        ident: T::ident(),
+        rust_path: rust_path::<T>(),
+        constructor: T::constructor().is_some(),
+        schema: (!T::schema(true).is_empty()).then(T::schema_var_name),
+    };
+
+    let config = Config::of::<T>()?;
+    let files = match config.output {
+        Output::TypeScript => vec![(path.to_owned(), Contents::All)],
+        Output::JavaScript => vec![
+            (path.with_extension("d.ts"), Contents::Types),
+            (path.with_extension("js"), Contents::Values),
+        ],
+    };
+
+    for (file, contents) in files {
+        let started = Instant::now();
+        let buffer = format(config, &file, generate::<T>(contents)?)?;
+
+        let on_disk = sink.path_on_disk(&file).map(path::absolute).transpose()?;
+        let checked = match &on_disk {
+            Some(on_disk) => check::record(config, on_disk, &file, &entry.ident, &buffer)?,
+            None => false,
+        };
+        if !checked {
+            export_and_merge(session, sink, config, &file, on_disk, &entry, buffer)?;
         }
-    }
 
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        session.report.record(&file, &entry, started.elapsed());
     }
 
-    export_and_merge(path, type_name, buffer)?;
+    // in check mode, the barrel is checked as well
+    if cfg!(feature = "barrel") {
+        barrel::update(sink, &mut session.report, config, path, &entry)?;
+    }
 
     Ok(())
 }
 
-/// Exports the type to a new file if the file hasn't yet been written to.
-/// Otherwise, finds its place in the already existing file and inserts it.
+/// Exports the type to a new file if the file doesn't exist yet. Otherwise, inserts it into the
+/// existing file, replacing its previous declarations. Declarations of types which are no longer
+/// exported are removed by [`prune`](crate::prune).
+/// The header of the file is replaced as well.
+///
+/// Reading the file locks the export directory of a [`Directory`], so that other threads and
+/// processes exporting into the same directory wait for us.
+#[allow(clippy::too_many_arguments)]
 fn export_and_merge(
-    path: PathBuf,
-    type_name: String,
+    session: &mut Session,
+    sink: &mut (impl ExportSink + ?Sized),
+    config: &Config,
+    path: &Path,
+    on_disk: Option<PathBuf>,
+    entry: &barrel::Entry,
     generated_type: String,
 ) -> Result<(), ExportError> {
-    use std::io::{Read, Write};
-
-    let mut lock = EXPORT_PATHS.lock().unwrap();
-
-    let Some(entry) = lock.get_mut(&path) else {
-        // The file hasn't been written to yet, so it must be
-        // overwritten
-        let mut file = File::create(&path)?;
-        file.write_all(generated_type.as_bytes())?;
-        file.sync_all()?;
-
-        let mut set = HashSet::new();
-        set.insert(type_name);
-        lock.insert(path, set);
+    let type_name = &entry.ident;
+    let existing = sink.read(path)?;
 
-        return Ok(());
+    let mut exported = EXPORT_PATHS.lock().unwrap_or_else(|e| e.into_inner());
+    let types = match on_disk {
+        Some(on_disk) => exported.entry(on_disk).or_default(),
+        None => session.declared.entry(path.to_owned()).or_default(),
     };
 
-    if entry.contains(&type_name) {
-        return Ok(());
+    // types with the same name can't be declared within the same file
+    match types.get(type_name) {
+        Some(&other) if other == entry.rust_path => return sink.declared(path, type_name),
+        Some(&other) => return Err(name_collision(type_name, other, entry.rust_path)),
+        None => (),
     }
 
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .open(&path)?;
-
-    let file_len = file.metadata()?.len();
-
-    let mut original_contents = String::with_capacity(file_len as usize);
-    file.read_to_string(&mut original_contents)?;
-
-    let buffer = merge(original_contents, generated_type);
-
-    file.seek(SeekFrom::Start(NOTE.len() as u64))?;
-
-    file.write_all(buffer.as_bytes())?;
-    file.sync_all()?;
+    let body = match &existing {
+        Some(existing) => {
+            let existing = header::strip(config, existing).unwrap_or(existing);
+            merge::upsert(existing, type_name, &generated_type)
+        }
+        None => generated_type,
+    };
 
-    entry.insert(type_name);
+    let contents = header::stamp(config, path, &body);
+    session.report.write(sink, path, contents)?;
+    sink.declared(path, type_name)?;
+    types.insert(type_name.clone(), entry.rust_path);
 
     Ok(())
 }
 
-const HEADER_ERROR_MESSAGE: &'static str = "The generated strings must have their NOTE and imports separated from their type declarations by a new line";
-
-const DECLARATION_START: &'static str = "export type ";
-
-/// Inserts the imports and declaration from the newly generated type
-/// into the contents of the file, removimg duplicate imports and organazing
-/// both imports and declarations alphabetically
-fn merge(original_contents: String, new_contents: String) -> String {
-    let (original_header, original_decls) = original_contents
-        .split_once("\n\n")
-        .expect(HEADER_ERROR_MESSAGE);
-    let (new_header, new_decl) = new_contents.split_once("\n\n").expect(HEADER_ERROR_MESSAGE);
-
-    let imports = original_header
-        .lines()
-        .skip(1)
-        .chain(new_header.lines().skip(1))
-        .collect::<BTreeSet<_>>();
-
-    let import_len = imports.iter().map(|&x| x.len()).sum::<usize>() + imports.len();
-    let capacity = import_len + original_decls.len() + new_decl.len() + 2;
-
-    let mut buffer = String::with_capacity(capacity);
-
-    for import in imports {
-        buffer.push_str(import);
-        buffer.push('\n')
-    }
-
-    let new_decl = new_decl.trim_matches('\n');
-
-    let new_decl_name = new_decl
-        .split(DECLARATION_START)
-        .last()
-        .unwrap()
-        .split_whitespace()
-        .next()
-        .unwrap();
-
-    let original_decls = original_decls.split("\n\n").map(|x| x.trim_matches('\n'));
-
-    let mut inserted = false;
-    for decl in original_decls {
-        let decl_name = decl
-            .split(DECLARATION_START)
-            .last()
-            .unwrap()
-            .split_whitespace()
-            .next()
-            .unwrap();
-
-        if inserted || decl_name < new_decl_name {
-            buffer.push('\n');
-            buffer.push_str(decl);
-            buffer.push('\n');
-        } else {
-            buffer.push('\n');
-            buffer.push_str(new_decl);
-            buffer.push('\n');
-
-            buffer.push('\n');
-            buffer.push_str(decl);
-            buffer.push('\n');
+/// Formats the bindings which will be written to `path`, without their header, as configured by
+/// `config`
+#[allow(unused_variables)]
+fn format(config: &'static Config, path: &Path, buffer: String) -> Result<String, ExportError> {
+    let Some(format) = &config.format else {
+        return Ok(buffer);
+    };
 
-            inserted = true;
+    match format.formatter {
+        #[cfg(feature = "format")]
+        crate::Formatter::Dprint => {
+            use dprint_plugin_typescript::format_text;
+
+            let configuration = dprint::configuration(config)?;
+            // the header is added afterwards, but without one, leading empty lines would be removed
+            let file = format!("{}\n{}", NOTE, buffer);
+            let formatted = format_text(path, &file, configuration)
+                .map_err(|e| ExportError::Formatting(e.to_string()))?;
+            Ok(match formatted {
+                Some(formatted) => match formatted.strip_prefix(&format!("{}\n", NOTE)) {
+                    Some(body) => body.to_owned(),
+                    None => formatted,
+                },
+                None => buffer,
+            })
         }
+        // without the `format` feature, dprint can't be configured as formatter
+        _ => Ok(pretty::pretty_print(&buffer, format)),
     }
+}
 
-    if !inserted {
-        buffer.push('\n');
-        buffer.push_str(new_decl);
-        buffer.push('\n');
+/// Writes `contents` to a temporary file, which is then moved to `path`. This way, readers never
+/// observe a partially written file.
+fn write_atomic(path: &Path, contents: &str) -> Result<(), ExportError> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp, path));
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
     }
-
-    buffer
+    Ok(result?)
 }
 
 /// Returns the generated definition for `T`.
 pub(crate) fn export_to_string<T: TS + ?Sized + 'static>() -> Result<String, ExportError> {
+    let path = T::output_path().unwrap_or(Path::new(""));
+    let body = generate::<T>(Contents::All)?;
+    Ok(header::stamp(Config::of::<T>()?, path, &body))
+}
+
+/// The bindings of a type within a file
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Contents {
+    /// Types and values in TypeScript
+    All,
+    /// Type declarations, written to a `.d.ts` file
+    Types,
+    /// Values in JavaScript, written to a `.js` file
+    Values,
+}
+
+/// Returns the `contents` of the file `T` is exported to, without its header.
+fn generate<T: TS + ?Sized + 'static>(contents: Contents) -> Result<String, ExportError> {
+    let config = Config::of::<T>()?;
     let mut buffer = String::with_capacity(1024);
-    buffer.push_str(NOTE);
-    generate_imports::<T::WithoutGenerics>(&mut buffer, default_out_dir())?;
-    generate_decl::<T>(&mut buffer);
-    generate_schema::<T>(&mut buffer);
+    // the dependencies of `T`, and therefore its imports, depend on the options as well, e.g. the
+    // types an interface extends
+    let options = RenderOptions::current().configure(config);
+    options.scope(|| {
+        let aliases = generate_imports::<T::WithoutGenerics>(
+            &mut buffer,
+            config,
+            default_out_dir(),
+            contents,
+        )?;
+        render::with_aliases(aliases, || match contents {
+            Contents::All => {
+                generate_decl::<T>(&mut buffer);
+                generate_schema::<T>(&mut buffer);
+            }
+            Contents::Types => generate_declarations::<T>(&mut buffer),
+            Contents::Values => generate_values::<T>(&mut buffer),
+        });
+        Ok::<_, ExportError>(())
+    })?;
     buffer.push('\n');
     Ok(buffer)
 }
 
+/// Returns the directory set by `TS_RS_EXPORT_DIR`, or the one configured by the [`Config`].
 pub(crate) fn default_out_dir() -> Cow<'static, Path> {
-    match std::env::var("TS_RS_EXPORT_DIR") {
+    match Config::current() {
+        Ok(config) => Cow::Owned(config.export_dir()),
+        // the error is reported once the bindings are generated
         Err(..) => Cow::Borrowed(Path::new("./bindings")),
-        Ok(dir) => Cow::Owned(PathBuf::from(dir)),
     }
 }
 
@@ -287,6 +374,12 @@ fn generate_decl<T: TS + ?Sized>(out: &mut String) {
     // Type Definition
     out.push_str("export ");
     out.push_str(&T::decl());
+
+    // Constructor of branded types
+    if let Some(constructor) = T::constructor() {
+        out.push_str("\nexport ");
+        out.push_str(&constructor);
+    }
 }
 
 /// Push the schema of `T`
@@ -298,11 +391,56 @@ fn generate_schema<T: TS + ?Sized>(out: &mut String) {
     }
 }
 
-/// Push an import statement for all dependencies of `T`.
+/// Push the declarations of `T` and of the values exported alongside it, which are written to
+/// a `.d.ts` file
+fn generate_declarations<T: TS + ?Sized>(out: &mut String) {
+    if let Some(docs) = &T::DOCS {
+        out.push_str(docs);
+    }
+    out.push_str("export ");
+    out.push_str(&T::decl());
+
+    if let Some((name, signature)) = T::constructor().as_deref().and_then(constructor_signature) {
+        write!(out, "\nexport declare const {}: {};", name, signature).unwrap();
+    }
+
+    if !T::schema(true).is_empty() {
+        let name = T::schema_var_name();
+        write!(out, "\n\nexport declare const {}: Record<string, unknown>;", name).unwrap();
+    }
+}
+
+/// Push the values exported alongside `T` in JavaScript, which are written to a `.js` file
+fn generate_values<T: TS + ?Sized>(out: &mut String) {
+    let mut values = Vec::new();
+    if let Some((name, _)) = T::constructor().as_deref().and_then(constructor_signature) {
+        values.push(format!("export const {} = (value) => value;", name));
+    }
+    let schema = T::schema(true);
+    if !schema.is_empty() {
+        values.push(format!("export {}", schema));
+    }
+    out.push_str(&values.join("\n\n"));
+}
+
+/// Splits the constructor of a branded type, `const X = (value: B): X => value as X;`, into its
+/// name and its signature `(value: B) => X`.
+fn constructor_signature(constructor: &str) -> Option<(&str, String)> {
+    let (name, function) = constructor.strip_prefix("const ")?.split_once(" = ")?;
+    let (signature, _) = function.rsplit_once(" => ")?;
+    let (parameters, returns) = signature.rsplit_once("): ")?;
+    Some((name, format!("{}) => {}", parameters, returns)))
+}
+
+/// Push an import statement for all dependencies of `T` needed by the `contents` of its file,
+/// returning the aliases under which dependencies are imported if their names collide with
+/// another type.
 fn generate_imports<T: TS + ?Sized + 'static>(
     out: &mut String,
+    config: &Config,
     out_dir: impl AsRef<Path>,
-) -> Result<(), ExportError> {
+    contents: Contents,
+) -> Result<HashMap<&'static str, String>, ExportError> {
     let path = T::output_path()
         .ok_or_else(std::any::type_name::<T>)
         .map(|x| out_dir.as_ref().join(x))
@@ -310,71 +448,168 @@ fn generate_imports<T: TS + ?Sized + 'static>(
 
     // Collect type dependencies (for `import type { X }`)
     let deps = T::dependencies();
-    let deduplicated_deps: BTreeMap<&str, &Dependency> = deps
-        .iter()
-        .filter(|dep| dep.type_id != TypeId::of::<T>())
-        .map(|dep| (dep.ts_name.as_str(), dep))
-        .collect();
-
     // Collect schema dependencies (for `import { XSchema }`) — may include
     // types not present in the regular TS type deps (e.g. #[ts(inline)] types)
     let schema_deps = T::schema_dependencies();
-    let deduplicated_schema_deps: BTreeMap<&str, &Dependency> = schema_deps
-        .iter()
-        .filter(|dep| dep.type_id != TypeId::of::<T>())
-        .map(|dep| (dep.ts_name.as_str(), dep))
-        .collect();
-
-    // Merged sorted set of all dependency names
-    let all_names: BTreeSet<&str> = deduplicated_deps
-        .keys()
-        .chain(deduplicated_schema_deps.keys())
-        .copied()
-        .collect();
-
-    for name in all_names {
-        // Prefer schema_dep for path info (they overlap most of the time)
-        let dep = deduplicated_schema_deps
-            .get(name)
-            .or_else(|| deduplicated_deps.get(name))
-            .unwrap();
 
-        let dep_path = out_dir.as_ref().join(dep.output_path);
-        let rel_path = import_path(&path, &dep_path)?;
+    // Dependencies by their name and the rust type declaring them, so that different instances of
+    // a generic type are only imported once
+    let mut imports: BTreeMap<(&str, &'static str), Import> = BTreeMap::new();
+    let all_deps = deps.iter().map(|dep| (dep, false));
+    for (dep, schema) in all_deps.chain(schema_deps.iter().map(|dep| (dep, true))) {
+        if dep.type_id == TypeId::of::<T>() {
+            continue;
+        }
+        let import = imports
+            .entry((dep.ts_name.as_str(), dep.rust_path))
+            .or_insert(Import {
+                dep,
+                ty: false,
+                schema: false,
+            });
+        match schema {
+            true => import.schema = true,
+            false => import.ty = true,
+        }
+    }
+
+    // Names declared within the file, together with the rust type declaring them
+    let mut names = HashMap::new();
+    claim(&mut names, &T::ident(), rust_path::<T>())?;
+
+    let mut imported = Vec::with_capacity(imports.len());
+    for import in imports.values() {
+        let dep_path = out_dir.as_ref().join(import.dep.output_path);
+        let rel_path = import_path(config, &path, &dep_path)?;
 
         let is_same_file = path
             .file_name()
             .and_then(std::ffi::OsStr::to_str)
             .map(|x| x.trim_end_matches(".ts"))
             .map(|x| format!("./{x}"))
-            .map(|x| x == rel_path.trim_end_matches(".js"))
+            .map(|x| x == rel_path.trim_end_matches(".js").trim_end_matches(".ts"))
             .unwrap_or(false);
 
-        if is_same_file {
-            continue;
+        match is_same_file {
+            true => claim(&mut names, &import.dep.ts_name, import.dep.rust_path)?,
+            false => imported.push((import, rel_path)),
         }
+    }
 
-        if deduplicated_deps.contains_key(name) {
+    let mut aliases = HashMap::new();
+    for &(import, ref rel_path) in &imported {
+        let Import { dep, ty, schema } = *import;
+        let name = dep.ts_name.as_str();
+
+        // the type this import collides with, either declared within the file or imported as well
+        let other = names
+            .get(name)
+            .copied()
+            .filter(|other| *other != dep.rust_path)
+            .or_else(|| {
+                imported
+                    .iter()
+                    .map(|(import, _)| import.dep)
+                    .find(|other| other.ts_name == name && other.rust_path != dep.rust_path)
+                    .map(|other| other.rust_path)
+            });
+        let local_name = match other {
+            None => name.to_owned(),
+            Some(_) if cfg!(feature = "import-aliases") => {
+                let alias = alias(name, dep.rust_path);
+                aliases.insert(dep.rust_path, alias.clone());
+                alias
+            }
+            Some(other) => return Err(name_collision(name, other, dep.rust_path)),
+        };
+        claim(&mut names, &local_name, dep.rust_path)?;
+
+        let (type_import, schema_import) = match local_name == name {
+            true => (name.to_owned(), dep.schema_var_name.clone()),
+            false => (
+                format!("{} as {}", name, local_name),
+                format!("{} as {}Schema", dep.schema_var_name, local_name),
+            ),
+        };
+        if ty && contents != Contents::Values {
             writeln!(
                 out,
                 r#"import type {{ {} }} from "{}";"#,
-                name, rel_path
+                type_import, rel_path
             )?;
         }
-        if deduplicated_schema_deps.contains_key(name) {
+        if schema && contents != Contents::Types {
             writeln!(
                 out,
                 r#"import {{ {} }} from "{}";"#,
-                dep.schema_var_name, rel_path
+                schema_import, rel_path
             )?;
         }
     }
     writeln!(out)?;
-    Ok(())
+    Ok(aliases)
 }
 
-/// Returns the required import path for importing `import` from the file `from`
-fn import_path(from: &Path, import: &Path) -> Result<String, ExportError> {
+/// A dependency which is imported as a type, its schema, or both
+struct Import<'a> {
+    dep: &'a Dependency,
+    ty: bool,
+    schema: bool,
+}
+
+/// Claims `name` within a file for the rust type `rust_path`, failing if another type already
+/// uses it.
+fn claim(
+    names: &mut HashMap<String, &'static str>,
+    name: &str,
+    rust_path: &'static str,
+) -> Result<(), ExportError> {
+    match names.insert(name.to_owned(), rust_path) {
+        Some(other) if other != rust_path => Err(name_collision(name, other, rust_path)),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the alias of the type `name` declared by `rust_path`, prefixed by its module, e.g
+/// `B_Error` for `a::b::Error`.
+fn alias(name: &str, rust_path: &'static str) -> String {
+    let module = without_generics(rust_path)
+        .rsplit("::")
+        .nth(1)
+        .unwrap_or_default()
+        .trim_start_matches("r#");
+    let mut chars = module.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}_{}", first.to_uppercase(), chars.as_str(), name),
+        None => name.to_owned(),
+    }
+}
+
+fn name_collision(name: &str, first: &'static str, second: &'static str) -> ExportError {
+    // the order in which dependencies are visited is not stable across builds
+    let (first, second) = match first < second {
+        true => (first, second),
+        false => (second, first),
+    };
+    ExportError::NameCollision {
+        name: name.to_owned(),
+        first: without_generics(first),
+        second: without_generics(second),
+    }
+}
+
+/// Identifies the declaration of `T`, which is shared by all instances of a generic type
+pub(crate) fn rust_path<T: TS + ?Sized>() -> &'static str {
+    std::any::type_name::<T::WithoutGenerics>()
+}
+
+fn without_generics(rust_path: &'static str) -> &'static str {
+    rust_path.split('<').next().unwrap_or(rust_path)
+}
+
+/// Returns the required import path for importing `import` from the file `from`, using the
+/// import extension of `config`
+fn import_path(config: &Config, from: &Path, import: &Path) -> Result<String, ExportError> {
     let rel_path = diff_paths(import, from.parent().unwrap())?;
     let str_path = match rel_path.components().next() {
         Some(Component::Normal(_)) => {
@@ -391,9 +626,14 @@ fn import_path(from: &Path, import: &Path) -> Result<String, ExportError> {
 
     let path_without_extension = path.trim_end_matches(".ts");
 
-    Ok(if cfg!(feature = "import-esm") {
-        format!("{}.js", path_without_extension)
-    } else {
-        path_without_extension.to_owned()
+    // ES modules require the extension, and `.js` files can't import `.ts` files
+    let extension = match config.output {
+        Output::TypeScript => config.import_extension,
+        Output::JavaScript => ImportExtension::Js,
+    };
+    Ok(match extension {
+        ImportExtension::None => path_without_extension.to_owned(),
+        ImportExtension::Js => format!("{}.js", path_without_extension),
+        ImportExtension::Ts => format!("{}.ts", path_without_extension),
     })
 }
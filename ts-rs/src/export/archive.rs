@@ -0,0 +1,112 @@
+//! Sinks writing bindings into tar and zip archives.
+//!
+//! Since multiple types may be exported into the same file, files are kept in memory until the
+//! archive is finished.
+
+#[cfg(feature = "zip-archive")]
+use std::io::Seek;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use super::{sink::ExportSink, ExportError};
+
+/// Writes bindings into a tar archive once [`TarArchive::finish`] is called.
+///
+/// Entries are written in a deterministic order and without timestamps, so exporting the same
+/// types twice results in identical archives.
+#[cfg(feature = "tar-archive")]
+pub struct TarArchive<W: Write> {
+    writer: W,
+    files: BTreeMap<PathBuf, String>,
+}
+
+#[cfg(feature = "tar-archive")]
+impl<W: Write> TarArchive<W> {
+    /// Creates a sink writing a tar archive into `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Writes all exported files into the archive, returning the underlying writer.
+    pub fn finish(self) -> Result<W, ExportError> {
+        let mut builder = tar::Builder::new(self.writer);
+        for (path, contents) in &self.files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(0);
+            builder.append_data(&mut header, entry_name(path), contents.as_bytes())?;
+        }
+        Ok(builder.into_inner()?)
+    }
+}
+
+#[cfg(feature = "tar-archive")]
+impl<W: Write> ExportSink for TarArchive<W> {
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        self.files.read(path)
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        self.files.write(path, contents)
+    }
+}
+
+/// Writes bindings into a zip archive once [`ZipArchive::finish`] is called.
+///
+/// Entries are written in a deterministic order and compressed using deflate.
+#[cfg(feature = "zip-archive")]
+pub struct ZipArchive<W: Write + Seek> {
+    writer: W,
+    files: BTreeMap<PathBuf, String>,
+}
+
+#[cfg(feature = "zip-archive")]
+impl<W: Write + Seek> ZipArchive<W> {
+    /// Creates a sink writing a zip archive into `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Writes all exported files into the archive, returning the underlying writer.
+    pub fn finish(self) -> Result<W, ExportError> {
+        use zip::{write::SimpleFileOptions, CompressionMethod, DateTime};
+
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .unix_permissions(0o644);
+
+        let mut zip = zip::ZipWriter::new(self.writer);
+        for (path, contents) in &self.files {
+            zip.start_file(entry_name(path), options)?;
+            zip.write_all(contents.as_bytes())?;
+        }
+        Ok(zip.finish()?)
+    }
+}
+
+#[cfg(feature = "zip-archive")]
+impl<W: Write + Seek> ExportSink for ZipArchive<W> {
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        self.files.read(path)
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        self.files.write(path, contents)
+    }
+}
+
+/// Name of the entry of the file at `path` within an archive, which always uses `/` as separator
+fn entry_name(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
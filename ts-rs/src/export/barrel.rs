@@ -0,0 +1,138 @@
+//! Barrel files, which re-export all types within a directory from a single `index.ts`.
+//!
+//! If bindings are exported as JavaScript, the barrel is split into an `index.d.ts`, re-exporting
+//! all types and values, and an `index.js`, re-exporting only the values.
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use super::{check, header, import_path, path, ExportError, ExportReport, ExportSink};
+use crate::{Config, Output};
+
+/// Name of the barrel file within every directory
+pub(crate) const INDEX: &str = "index.ts";
+
+/// A type exported to a file, which is re-exported from the barrel of its directory
+pub(crate) struct Entry {
+    pub ident: String,
+    /// Path of the rust type, see [`Dependency::rust_path`](crate::Dependency::rust_path)
+    pub rust_path: &'static str,
+    /// Whether the type has a constructor, in which case it's exported as a value as well
+    pub constructor: bool,
+    /// Name of the schema exported alongside the type, if any
+    pub schema: Option<String>,
+}
+
+/// Adds `entry`, which was exported to `file`, to the barrel in the same directory. `config` is
+/// the configuration of the crate declaring the type.
+///
+/// Like the bindings of types, the entry is merged into the existing barrel, and re-exports of types
+/// which are no longer exported are removed by [`prune`](crate::prune). In check mode, the barrel
+/// on disk is checked instead.
+pub(crate) fn update(
+    sink: &mut (impl ExportSink + ?Sized),
+    report: &mut ExportReport,
+    config: &'static Config,
+    file: &Path,
+    entry: &Entry,
+) -> Result<(), ExportError> {
+    let Some(index) = index_of(file) else {
+        return Ok(());
+    };
+
+    let from = import_path(config, &index, file)?;
+    let ident = &entry.ident;
+    let mut lines = vec![match entry.constructor {
+        true => format!(r#"export {{ {ident} }} from "{from}";"#),
+        false => format!(r#"export type {{ {ident} }} from "{from}";"#),
+    }];
+    if let Some(schema) = &entry.schema {
+        lines.push(format!(r#"export {{ {schema} }} from "{from}";"#));
+    }
+
+    match config.output {
+        Output::TypeScript => insert(sink, report, config, &index, lines),
+        Output::JavaScript => {
+            let types = index.with_extension("d.ts");
+            insert(sink, report, config, &types, lines.clone())?;
+            lines.retain(|line| !line.starts_with("export type "));
+            insert(sink, report, config, &index.with_extension("js"), lines)
+        }
+    }
+}
+
+/// Adds the re-exports `lines` to the barrel `index`
+fn insert(
+    sink: &mut (impl ExportSink + ?Sized),
+    report: &mut ExportReport,
+    config: &'static Config,
+    index: &Path,
+    lines: Vec<String>,
+) -> Result<(), ExportError> {
+    if let Some(on_disk) = sink.path_on_disk(index).map(path::absolute).transpose()? {
+        if check::record_with(config, &on_disk, index, |body| merge(body, &lines))? {
+            return Ok(());
+        }
+    }
+
+    let existing = sink.read(index)?.unwrap_or_default();
+    let contents = header::stamp(config, index, &merge(&existing, &lines));
+    report.write(sink, index, contents)?;
+    for name in lines.iter().filter_map(|line| exported(line)) {
+        sink.declared(index, name)?;
+    }
+    Ok(())
+}
+
+/// Removes the re-exports of all names but `declared` from the body of a barrel, returning `None`
+/// if there are none to remove.
+pub(crate) fn retain(body: &str, declared: &BTreeSet<String>) -> Option<String> {
+    let mut removed = false;
+    let retained = body
+        .lines()
+        .filter(|line| {
+            let keep = exported(line).map_or(true, |name| declared.contains(name));
+            removed |= !keep;
+            keep
+        })
+        .map(|line| format!("{line}\n"))
+        .collect::<String>();
+    removed.then(|| merge(&retained, &[]))
+}
+
+/// Returns the name re-exported by a line of a barrel
+fn exported(line: &str) -> Option<&str> {
+    let (_, rest) = line.strip_prefix("export ")?.split_once("{ ")?;
+    rest.split_once(" }").map(|(name, _)| name)
+}
+
+/// Returns the body of a barrel containing the re-exports within `existing` and `lines`
+fn merge(existing: &str, lines: &[String]) -> String {
+    let lines = existing
+        .lines()
+        .filter(|line| line.starts_with("export "))
+        .map(str::to_owned)
+        .chain(lines.iter().cloned())
+        .collect::<BTreeSet<_>>();
+
+    let mut body = String::new();
+    for line in lines {
+        body.push_str(&line);
+        body.push('\n');
+    }
+    body
+}
+
+/// Returns true if `file` is a barrel, see [`update`]
+pub(crate) fn is_index(file: &Path) -> bool {
+    let name = file.file_name().and_then(|name| name.to_str());
+    matches!(name, Some(INDEX | "index.d.ts" | "index.js"))
+}
+
+/// Returns the path of the barrel in the directory of `file`, or `None` if `file` is the barrel
+fn index_of(file: &Path) -> Option<PathBuf> {
+    let index = file.with_file_name(INDEX);
+    (index != file).then_some(index)
+}
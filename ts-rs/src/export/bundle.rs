@@ -0,0 +1,323 @@
+//! Bundles, which contain the bindings of multiple types and all of their dependencies in a
+//! single, self-contained file.
+
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    path::Path,
+};
+
+use super::{
+    format, generate_decl, generate_schema, header, name_collision, rust_path, write_atomic,
+    ExportError,
+};
+use crate::{Config, RenderOptions, TypeVisitor, TS};
+
+/// A single TypeScript file containing the bindings of multiple types, including all of their
+/// dependencies.
+///
+/// Since every dependency is declared within the bundle, it does not contain any imports.
+/// Declarations are ordered so that every type is declared after the types it depends on.
+/// Schemas of types which depend on each other refer to the schemas declared after them lazily,
+/// using getters.
+///
+/// ```
+/// # use ts_rs::{Bundle, TS};
+/// #[derive(TS)]
+/// struct User {
+///     id: UserId,
+/// }
+///
+/// #[derive(TS)]
+/// struct UserId(u64);
+///
+/// let bindings = Bundle::new().add::<User>().export_to_string().unwrap();
+/// assert!(bindings.find("type UserId").unwrap() < bindings.find("type User ").unwrap());
+/// ```
+#[derive(Default)]
+pub struct Bundle {
+    seen: HashSet<TypeId>,
+    /// Declarations in the order they were encountered
+    decls: Vec<Decl>,
+    /// Index of the declaration of every rust type within `decls`
+    index: HashMap<&'static str, usize>,
+    /// Declarations of the types added to the bundle
+    roots: Vec<usize>,
+    /// Names declared within the bundle, together with the rust type declaring them
+    names: HashMap<String, &'static str>,
+    /// The first error which occurred while adding a type
+    error: Option<ExportError>,
+}
+
+/// The bindings of a single type
+struct Decl {
+    text: String,
+    /// Name and declaration of the schema of the type, if it has one
+    schema: Option<(String, String)>,
+    /// Rust types this type depends on
+    dependencies: Vec<&'static str>,
+}
+
+impl Bundle {
+    /// Creates an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `T` and all of its dependencies to the bundle.
+    ///
+    /// If `T` cannot be exported, or a name is declared by two different types, the error is
+    /// returned once the bundle is written.
+    pub fn add<T: TS + ?Sized + 'static>(mut self) -> Self {
+        if self.error.is_none() && T::output_path().is_none() {
+            let error = ExportError::CannotBeExported(std::any::type_name::<T>());
+            self.error = Some(error);
+        }
+
+        self.insert::<T>();
+        if let Some(&index) = self.index.get(rust_path::<T>()) {
+            self.roots.push(index);
+        }
+        self
+    }
+
+    /// Renders the bundle.  
+    /// Like [`TS::export_to_string`], this does not format the output, even if the `format`
+    /// feature is enabled.
+    pub fn export_to_string(self) -> Result<String, ExportError> {
+        let body = self.body()?;
+        Ok(header::stamp(Config::current()?, Path::new(""), &body))
+    }
+
+    /// Writes the bundle to `path`, replacing the file if it already exists.
+    pub fn write(self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        let path = path.as_ref();
+        let config = Config::current()?;
+        let body = format(config, path, self.body()?)?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_atomic(path, &header::stamp(config, path, &body))
+    }
+
+    /// Renders the bundle without its header
+    fn body(self) -> Result<String, ExportError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut order = Vec::with_capacity(self.decls.len());
+        let mut visited = vec![false; self.decls.len()];
+        for &root in &self.roots {
+            self.sort(root, &mut visited, &mut order);
+        }
+
+        // schemas which are not yet initialized, and ones which read them once they are accessed
+        let mut pending = (order.iter())
+            .filter_map(|&index| self.decls[index].schema.as_ref())
+            .map(|(name, _)| name.as_str())
+            .collect::<HashSet<_>>();
+        let mut lazy = HashSet::new();
+
+        let mut buffer = String::new();
+        for index in order {
+            let decl = &self.decls[index];
+            buffer.push('\n');
+            buffer.push_str(&decl.text);
+            if let Some((name, schema)) = &decl.schema {
+                let (schema, is_lazy) = defer_references(schema, &pending, &lazy);
+                buffer.push_str(&schema);
+                pending.remove(name.as_str());
+                if is_lazy {
+                    lazy.insert(name.as_str());
+                }
+            }
+            buffer.push('\n');
+        }
+        Ok(buffer)
+    }
+
+    /// Adds `T` and, recursively, all of its dependencies
+    fn insert<T: TS + ?Sized + 'static>(&mut self) {
+        if self.error.is_some() || T::output_path().is_none() {
+            return;
+        }
+        if !self.seen.insert(TypeId::of::<T>()) {
+            return;
+        }
+
+        // every type is rendered as configured by the crate declaring it
+        let options = match Config::of::<T>() {
+            Ok(config) => RenderOptions::current().configure(config),
+            Err(error) => {
+                self.error = Some(error);
+                return;
+            }
+        };
+
+        let mut visitor = Visit {
+            bundle: self,
+            dependencies: vec![],
+        };
+        options.scope(|| {
+            T::visit_dependencies(&mut visitor);
+            T::visit_schema_dependencies(&mut visitor);
+        });
+        let dependencies = visitor.dependencies;
+
+        // different instances of a generic type share their declaration
+        let rust_path = rust_path::<T>();
+        if let Some(&index) = self.index.get(rust_path) {
+            self.decls[index].dependencies.extend(dependencies);
+            return;
+        }
+
+        let schema = T::schema(true);
+        let mut names = vec![T::ident()];
+        if !schema.is_empty() {
+            names.push(T::schema_var_name());
+        }
+        for name in names {
+            match self.names.get(&name) {
+                Some(other) if *other != rust_path => {
+                    self.error = Some(name_collision(&name, other, rust_path));
+                    return;
+                }
+                _ => self.names.insert(name, rust_path),
+            };
+        }
+
+        let mut text = String::new();
+        let mut schema_text = String::new();
+        options.scope(|| {
+            generate_decl::<T>(&mut text);
+            generate_schema::<T>(&mut schema_text);
+        });
+        let schema = (!schema.is_empty()).then(|| (T::schema_var_name(), schema_text));
+
+        self.index.insert(rust_path, self.decls.len());
+        self.decls.push(Decl {
+            text,
+            schema,
+            dependencies,
+        });
+    }
+
+    /// Pushes the declaration at `index` to `order`, after all of its dependencies.
+    /// Types which depend on each other are ordered by when they were first encountered.
+    fn sort(&self, index: usize, visited: &mut [bool], order: &mut Vec<usize>) {
+        if std::mem::replace(&mut visited[index], true) {
+            return;
+        }
+        for dependency in &self.decls[index].dependencies {
+            if let Some(&dependency) = self.index.get(dependency) {
+                self.sort(dependency, visited, order);
+            }
+        }
+        order.push(index);
+    }
+}
+
+/// Turns every entry of `schema`, like `"Node": NodeSchema,`, which reads a `pending` schema while
+/// it is declared into a getter, so it is only read once it is accessed. Spreading a `lazy`
+/// schema accesses all of its entries, so it is deferred as well.
+///
+/// Returns whether any entry was deferred.
+fn defer_references(schema: &str, pending: &HashSet<&str>, lazy: &HashSet<&str>) -> (String, bool) {
+    let mut deferred = false;
+    let mut out = String::with_capacity(schema.len());
+    for line in schema.split_inclusive('\n') {
+        let (content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+        let entry = entry(content).filter(|(_, _, value)| {
+            references(value)
+                .into_iter()
+                .any(|(name, spread)| pending.contains(name) || (spread && lazy.contains(name)))
+        });
+        match entry {
+            Some((indent, key, value)) => {
+                deferred = true;
+                let value = value.strip_suffix(',').unwrap_or(value);
+                write!(
+                    out,
+                    "{}get {}() {{ return {}; }},{}",
+                    indent, key, value, newline
+                )
+                .unwrap();
+            }
+            None => out.push_str(line),
+        }
+    }
+    (out, deferred)
+}
+
+/// Splits an entry of an object literal spanning a single line, like `  "key": value,`, into its
+/// indentation, key and value
+fn entry(line: &str) -> Option<(&str, &str, &str)> {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    if !content.starts_with('"') {
+        return None;
+    }
+    let mut escaped = false;
+    let end = content[1..].char_indices().find_map(|(i, c)| match c {
+        '\\' if !escaped => {
+            escaped = true;
+            None
+        }
+        '"' if !escaped => Some(i + 2),
+        _ => {
+            escaped = false;
+            None
+        }
+    })?;
+    let value = content[end..].strip_prefix(": ")?;
+    Some((indent, &content[..end], value))
+}
+
+/// Returns the identifiers outside of string literals within `value`, and whether they are spread
+fn references(value: &str) -> Vec<(&str, bool)> {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = None;
+    let mut identifiers = vec![];
+    for (i, c) in value.char_indices().chain([(value.len(), ' ')]) {
+        if in_string {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => in_string = false,
+                _ => escaped = false,
+            }
+            continue;
+        }
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            start.get_or_insert(i);
+            continue;
+        }
+        if let Some(start) = start.take() {
+            identifiers.push((&value[start..i], value[..start].ends_with("...")));
+        }
+        in_string = c == '"';
+    }
+    identifiers
+}
+
+struct Visit<'a> {
+    bundle: &'a mut Bundle,
+    dependencies: Vec<&'static str>,
+}
+
+impl TypeVisitor for Visit<'_> {
+    fn visit<T: TS + 'static + ?Sized>(&mut self) {
+        if T::output_path().is_none() {
+            return;
+        }
+
+        self.dependencies.push(rust_path::<T>());
+        self.bundle.insert::<T>();
+    }
+}
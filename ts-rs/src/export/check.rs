@@ -0,0 +1,161 @@
+//! Check mode, in which the bindings on disk are compared with what would be exported instead of
+//! being overwritten.
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::{diff::unified_diff, header, manifest, merge::upsert, ExportError};
+use crate::Config;
+
+thread_local! {
+    static CHECKER: RefCell<Option<Checker>> = const { RefCell::new(None) };
+}
+
+/// Returns true if the environment variable `TS_RS_CHECK` is set, in which case exporting only
+/// checks whether the bindings on disk are up to date.
+pub(crate) fn enabled() -> bool {
+    std::env::var("TS_RS_CHECK").map_or(false, |v| !matches!(v.as_str(), "" | "0" | "false"))
+}
+
+/// Runs `export`, in check mode if it is enabled using `TS_RS_CHECK`.
+pub(crate) fn check_if_enabled<T>(
+    export: impl FnOnce() -> Result<T, ExportError>,
+) -> Result<T, ExportError> {
+    match enabled() {
+        true => check(export),
+        false => export(),
+    }
+}
+
+/// Runs `export` in check mode, returning [`ExportError::OutOfDate`] with a diff of every stale
+/// file afterwards.
+pub(crate) fn check<T>(export: impl FnOnce() -> Result<T, ExportError>) -> Result<T, ExportError> {
+    if active() {
+        return export();
+    }
+
+    CHECKER.with(|c| *c.borrow_mut() = Some(Checker::default()));
+    let result = export();
+    let checker = CHECKER.with(|c| c.borrow_mut().take()).unwrap_or_default();
+    let value = result?;
+
+    let mut diffs = vec![];
+    for (path, file) in &checker.files {
+        let expected = header::stamp(file.config, &file.relative, &file.expected);
+        if file.on_disk.as_deref() != Some(&expected) {
+            let on_disk = file.on_disk.as_deref().unwrap_or("");
+            diffs.push(unified_diff(path, on_disk, &expected));
+        }
+    }
+
+    match diffs.is_empty() {
+        true => Ok(value),
+        false => Err(ExportError::OutOfDate(diffs.join("\n"))),
+    }
+}
+
+/// Returns true if bindings are currently being checked instead of exported.
+pub(crate) fn active() -> bool {
+    CHECKER.with(|c| c.borrow().is_some())
+}
+
+/// If check mode is active, records the generated bindings of the type `ident`, which would be
+/// exported to `path`, and returns `true`. `relative` is the path of the file within the export
+/// directory.
+///
+/// Like exporting, the bindings are merged into the file on disk. Declarations within it which
+/// [`prune`](crate::prune) would remove are expected to be gone.
+pub(crate) fn record(
+    config: &'static Config,
+    path: &Path,
+    relative: &Path,
+    ident: &str,
+    generated: &str,
+) -> Result<bool, ExportError> {
+    CHECKER.with(|c| match c.borrow_mut().as_mut() {
+        None => Ok(false),
+        Some(checker) => {
+            let file = checker.file(config, path, relative)?;
+            file.expected = upsert(&file.expected, ident, generated);
+            Ok(true)
+        }
+    })
+}
+
+/// If check mode is active, records that the file at `path` would be updated by `update`, which
+/// returns the new body of the file given its expected body so far, and returns `true`.
+pub(crate) fn record_with(
+    config: &'static Config,
+    path: &Path,
+    relative: &Path,
+    update: impl FnOnce(&str) -> String,
+) -> Result<bool, ExportError> {
+    CHECKER.with(|c| match c.borrow_mut().as_mut() {
+        None => Ok(false),
+        Some(checker) => {
+            let file = checker.file(config, path, relative)?;
+            file.expected = update(&file.expected);
+            Ok(true)
+        }
+    })
+}
+
+#[derive(Default)]
+struct Checker {
+    files: BTreeMap<PathBuf, File>,
+}
+
+struct File {
+    /// The configuration of the types declared within the file
+    config: &'static Config,
+    relative: PathBuf,
+    on_disk: Option<String>,
+    /// The expected body of the file, without its header
+    expected: String,
+}
+
+impl Checker {
+    /// Returns the file at `path`, reading it from disk the first time. Declarations within it
+    /// which are stale are removed from its expected body.
+    fn file(
+        &mut self,
+        config: &'static Config,
+        path: &Path,
+        relative: &Path,
+    ) -> Result<&mut File, ExportError> {
+        if !self.files.contains_key(path) {
+            let on_disk = match std::fs::read_to_string(path) {
+                Ok(contents) => Some(contents),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            let mut expected = on_disk
+                .as_deref()
+                .map(|on_disk| header::strip(config, on_disk).unwrap_or(on_disk))
+                .unwrap_or_default()
+                .to_owned();
+
+            // the export directory is the ancestor of the file at `relative`
+            let out_dir = path.ancestors().nth(relative.components().count());
+            if let Some(out_dir) = out_dir {
+                if let Some(declared) = manifest::declared(out_dir, relative)? {
+                    expected = manifest::retain(relative, &expected, &declared).unwrap_or(expected);
+                }
+            }
+
+            let file = File {
+                config,
+                relative: relative.to_owned(),
+                on_disk,
+                expected,
+            };
+            self.files.insert(path.to_owned(), file);
+        }
+
+        Ok(self.files.get_mut(path).unwrap())
+    }
+}
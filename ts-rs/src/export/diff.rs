@@ -0,0 +1,103 @@
+use std::{fmt::Write, path::Path};
+
+/// Number of unchanged lines shown around every change
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Renders a unified diff turning `old` into `new`
+pub(crate) fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let ops = diff_lines(&old, &new);
+
+    // line numbers within `old` and `new` before each operation
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut o, mut n) = (0, 0);
+    for (op, _) in &ops {
+        positions.push((o, n));
+        match op {
+            Op::Equal => (o, n) = (o + 1, n + 1),
+            Op::Delete => o += 1,
+            Op::Insert => n += 1,
+        }
+    }
+    positions.push((o, n));
+
+    let mut out = String::new();
+    let _ = writeln!(out, "--- {} (on disk)", path.display());
+    let _ = writeln!(out, "+++ {} (expected)", path.display());
+
+    let mut changes = (0..ops.len()).filter(|&i| ops[i].0 != Op::Equal).peekable();
+    while let Some(first) = changes.next() {
+        let start = first.saturating_sub(CONTEXT);
+        let mut end = (first + 1 + CONTEXT).min(ops.len());
+        while let Some(&next) = changes.peek() {
+            if next > end + CONTEXT {
+                break;
+            }
+            end = (next + 1 + CONTEXT).min(ops.len());
+            changes.next();
+        }
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        let range = |start: usize, len: usize| match len {
+            0 => format!("{},0", start),
+            len => format!("{},{}", start + 1, len),
+        };
+        let _ = writeln!(
+            out,
+            "@@ -{} +{} @@",
+            range(old_start, old_end - old_start),
+            range(new_start, new_end - new_start)
+        );
+
+        for (op, line) in &ops[start..end] {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            let _ = writeln!(out, "{}{}", prefix, line);
+        }
+    }
+
+    out
+}
+
+/// Computes the operations turning `old` into `new` using the longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((Op::Equal, old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push((Op::Delete, old[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, new[j]));
+            j += 1;
+        }
+    }
+    ops
+}
@@ -0,0 +1,83 @@
+//! Configuration of [dprint](https://dprint.dev), which formats the bindings if the `format`
+//! feature is enabled.
+
+use dprint_core::configuration::{resolve_global_config, ConfigKeyMap, ConfigKeyValue};
+use dprint_plugin_typescript::configuration::{
+    resolve_config, Configuration, ConfigurationBuilder, QuoteStyle, SemiColons,
+};
+use std::{collections::HashMap, sync::Mutex};
+
+use lazy_static::lazy_static;
+
+use super::ExportError;
+use crate::{Config, Format, Quotes};
+
+lazy_static! {
+    /// The configurations of dprint, by the address of the [`Config`] they were loaded for
+    static ref CONFIGURATIONS: Mutex<HashMap<usize, &'static Result<Configuration, String>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Returns the configuration of dprint for `config`, which is loaded from a `dprint.json` if one
+/// is configured by [`Format::dprint_config`].
+pub(crate) fn configuration(
+    config: &'static Config,
+) -> Result<&'static Configuration, ExportError> {
+    let mut configurations = CONFIGURATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let configuration = configurations
+        .entry(config as *const Config as usize)
+        .or_insert_with(|| {
+            let format = config.format.clone().unwrap_or_default();
+            Box::leak(Box::new(load(&format)))
+        });
+    configuration
+        .as_ref()
+        .map_err(|e| ExportError::Formatting(e.clone()))
+}
+
+fn load(format: &Format) -> Result<Configuration, String> {
+    let Some(path) = &format.dprint_config else {
+        return Ok(ConfigurationBuilder::new()
+            .deno()
+            .indent_width(format.indent_width)
+            .line_width(format.line_width)
+            .quote_style(match format.quotes {
+                Quotes::Double => QuoteStyle::PreferDouble,
+                Quotes::Single => QuoteStyle::PreferSingle,
+            })
+            .semi_colons(match format.semicolons {
+                true => SemiColons::Prefer,
+                false => SemiColons::Asi,
+            })
+            .build());
+    };
+
+    let invalid = |message: String| {
+        format!(
+            "invalid dprint configuration `{}`: {}",
+            path.display(),
+            message
+        )
+    };
+    let contents = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let mut global: ConfigKeyMap =
+        serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+
+    // the settings of the typescript plugin override the ones applying to all plugins
+    let typescript = match global.shift_remove("typescript") {
+        Some(ConfigKeyValue::Object(typescript)) => typescript,
+        _ => ConfigKeyMap::new(),
+    };
+    let global = resolve_global_config(&mut global);
+    let resolved = resolve_config(typescript, &global.config);
+
+    match global
+        .diagnostics
+        .iter()
+        .chain(&resolved.diagnostics)
+        .next()
+    {
+        Some(diagnostic) => Err(invalid(diagnostic.to_string())),
+        None => Ok(resolved.config),
+    }
+}
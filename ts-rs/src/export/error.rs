@@ -4,7 +4,7 @@ pub enum ExportError {
     #[error("this type cannot be exported")]
     CannotBeExported(&'static str),
     #[cfg(feature = "format")]
-    #[error("an error occurred while formatting the generated typescript output")]
+    #[error("an error occurred while formatting the generated typescript output: {0}")]
     Formatting(String),
     #[error("an error occurred while performing IO")]
     Io(#[from] std::io::Error),
@@ -12,4 +12,22 @@ pub enum ExportError {
     ManifestDirNotSet,
     #[error("an error occurred while writing to a formatted buffer")]
     Fmt(#[from] std::fmt::Error),
+    #[error("the bindings on disk are out of date\n{0}")]
+    OutOfDate(String),
+    #[error("`{name}` is declared by both `{first}` and `{second}`")]
+    NameCollision {
+        name: String,
+        first: &'static str,
+        second: &'static str,
+    },
+    #[error("invalid configuration in `{}`: {message}", path.display())]
+    Config {
+        path: std::path::PathBuf,
+        message: String,
+    },
+    #[error("`{command}` failed\n{output}")]
+    Command { command: String, output: String },
+    #[cfg(feature = "zip-archive")]
+    #[error("an error occurred while writing the zip archive")]
+    Zip(#[from] zip::result::ZipError),
 }
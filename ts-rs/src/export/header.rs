@@ -0,0 +1,100 @@
+//! The header at the top of every generated file, see [`Config::header`].
+//!
+//! Files are generated without a header, which is only added right before they are written.
+//! When a file is read back, e.g to merge another type into it, its header is removed again.
+//! Since the header may contain a hash of the file, it is recognized by matching its lines
+//! against the template instead of comparing it byte by byte.
+
+use std::path::Path;
+
+use super::NOTE;
+use crate::Config;
+
+/// Placeholders which may be used within the template of the header
+const PLACEHOLDERS: [&str; 3] = ["{version}", "{hash}", "{file}"];
+
+/// Prepends the configured header to the `body` of the file at `path`, which is relative to the
+/// export directory.
+pub(crate) fn stamp(config: &Config, path: &Path, body: &str) -> String {
+    if config.header.is_empty() {
+        return body.to_owned();
+    }
+
+    let header = config
+        .header
+        .replace("{version}", env!("CARGO_PKG_VERSION"))
+        .replace("{hash}", &format!("{:016x}", fnv1a(body)))
+        .replace("{file}", &path.to_string_lossy().replace('\\', "/"));
+
+    let mut contents = String::with_capacity(header.len() + body.len() + 1);
+    contents.push_str(&header);
+    contents.push('\n');
+    contents.push_str(body);
+    contents
+}
+
+/// Returns the body of a generated file, or `None` if `contents` neither start with the
+/// configured header nor with the default one, which files exported before the header was
+/// configured start with.
+pub(crate) fn strip<'a>(config: &Config, contents: &'a str) -> Option<&'a str> {
+    strip_template(&config.header, contents).or_else(|| strip_template(NOTE, contents))
+}
+
+fn strip_template<'a>(template: &str, contents: &'a str) -> Option<&'a str> {
+    // without a header, generated files can't be recognized
+    if template.is_empty() {
+        return None;
+    }
+
+    let mut rest = contents;
+    for pattern in template.lines() {
+        let (line, remaining) = rest.split_once('\n')?;
+        if !matches(pattern, line.trim_end_matches('\r')) {
+            return None;
+        }
+        rest = remaining;
+    }
+    Some(rest)
+}
+
+/// Returns true if `line` is a line of the template `pattern` with its placeholders filled in
+fn matches(pattern: &str, line: &str) -> bool {
+    let segments = segments(pattern);
+    let (first, rest) = segments.split_first().expect("there's always a segment");
+    let Some(mut line) = line.strip_prefix(first) else {
+        return false;
+    };
+
+    let Some((last, middle)) = rest.split_last() else {
+        return line.is_empty();
+    };
+    for segment in middle {
+        match line.find(segment) {
+            Some(index) => line = &line[index + segment.len()..],
+            None => return false,
+        }
+    }
+    line.ends_with(last)
+}
+
+/// Splits `pattern` at its placeholders
+fn segments(mut pattern: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    while let Some((index, placeholder)) = PLACEHOLDERS
+        .iter()
+        .filter_map(|p| pattern.find(p).map(|index| (index, p)))
+        .min()
+    {
+        segments.push(&pattern[..index]);
+        pattern = &pattern[index + placeholder.len()..];
+    }
+    segments.push(pattern);
+    segments
+}
+
+/// The 64 bit FNV-1a hash, which unlike the hashers of `std` is guaranteed to be stable
+pub(crate) fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,261 @@
+//! The manifest keeps track of the files written into an export directory, and of the types
+//! declared within them, so that bindings which are no longer generated can be pruned.
+//!
+//! The manifest also serves as an advisory lock on the export directory, which is held while a
+//! file is written. This makes exporting safe when multiple processes, e.g test binaries, export
+//! into the same directory at once.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs::File,
+    io::{ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use fs2::FileExt;
+
+use super::{barrel, header, merge, path, write_atomic, ExportError};
+use crate::Config;
+
+/// Name of the manifest within the export directory
+pub(crate) const MANIFEST: &str = ".ts-rs-manifest.json";
+
+/// The manifest of an export directory, exclusively locked until dropped.
+pub(crate) struct Manifest {
+    out_dir: PathBuf,
+    file: File,
+    /// The files written since the directory was last pruned, relative to it, together with the
+    /// types declared within them. `None` if nothing was written since.
+    files: Option<BTreeMap<PathBuf, BTreeSet<String>>>,
+}
+
+impl Manifest {
+    /// Opens the manifest of `out_dir`, waiting until no other thread or process holds it.
+    pub(crate) fn lock(out_dir: &Path) -> Result<Self, ExportError> {
+        let out_dir = path::absolute(out_dir)?;
+        std::fs::create_dir_all(&out_dir)?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(out_dir.join(MANIFEST))?;
+        file.lock_exclusive()?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        Ok(Self {
+            out_dir,
+            file,
+            files: parse(&contents),
+        })
+    }
+
+    /// Records that `file` was written, declaring the type `declaration` if it's not `None`.
+    pub(crate) fn record(
+        &mut self,
+        file: &Path,
+        declaration: Option<&str>,
+    ) -> Result<(), ExportError> {
+        let Some(file) = self.relative(file) else {
+            // the file was exported outside of the export directory, so it's never pruned
+            return Ok(());
+        };
+
+        let files = self.files.get_or_insert_with(BTreeMap::new);
+        let changed = match (files.get_mut(&file), declaration) {
+            (Some(declared), Some(declaration)) => declared.insert(declaration.to_owned()),
+            (Some(_), None) => false,
+            (None, declaration) => {
+                files.insert(file, declaration.map(str::to_owned).into_iter().collect());
+                true
+            }
+        };
+        if changed {
+            self.write()?;
+        }
+        Ok(())
+    }
+
+    fn relative(&self, file: &Path) -> Option<PathBuf> {
+        file.strip_prefix(&self.out_dir).ok().map(Path::to_owned)
+    }
+
+    fn write(&mut self) -> Result<(), ExportError> {
+        let mut out = String::new();
+        if let Some(files) = &self.files {
+            out.push_str("{\n  \"files\": {\n");
+            for (i, (file, declared)) in files.iter().enumerate() {
+                let file = file
+                    .to_string_lossy()
+                    .replace('\\', "/")
+                    .replace('"', "\\\"");
+                let declared = declared
+                    .iter()
+                    .map(|ident| format!("\"{}\"", ident))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let separator = if i + 1 < files.len() { "," } else { "" };
+                writeln!(out, "    \"{}\": [{}]{}", file, declared, separator)?;
+            }
+            out.push_str("  }\n}\n");
+        }
+
+        // the manifest is locked, so it's rewritten in place
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(out.as_bytes())?;
+        self.file.sync_all()?;
+        Ok(())
+    }
+}
+
+impl Drop for Manifest {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Parses the files listed in the manifest, or `None` if it is empty
+fn parse(contents: &str) -> Option<BTreeMap<PathBuf, BTreeSet<String>>> {
+    if contents.trim().is_empty() {
+        return None;
+    }
+
+    // the manifest is always written by `Manifest::write`, with one file per line. Manifests of
+    // previous versions list the files without the types declared within them.
+    let files = contents
+        .lines()
+        .map(|line| line.trim().trim_end_matches(','))
+        .filter_map(|line| {
+            let line = line.strip_prefix('"')?;
+            let (file, declared) = match line.split_once("\": [") {
+                Some((file, declared)) => (file, declared.strip_suffix(']')?),
+                None => (line.strip_suffix('"')?, ""),
+            };
+            let declared = declared
+                .split(',')
+                .map(|ident| ident.trim().trim_matches('"'))
+                .filter(|ident| !ident.is_empty())
+                .map(str::to_owned)
+                .collect();
+            Some((PathBuf::from(file.replace("\\\"", "\"")), declared))
+        })
+        .collect();
+    Some(files)
+}
+
+/// Returns the types declared within `file`, relative to `out_dir`, since the directory was last
+/// pruned, or `None` if nothing was exported into it since.
+/// The manifest is only read, so this doesn't wait for other threads or processes exporting into
+/// the directory.
+pub(crate) fn declared(
+    out_dir: &Path,
+    file: &Path,
+) -> Result<Option<BTreeSet<String>>, ExportError> {
+    let contents = match std::fs::read_to_string(out_dir.join(MANIFEST)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(parse(&contents).map(|mut files| files.remove(file).unwrap_or_default()))
+}
+
+/// Deletes all files generated by ts-rs within `out_dir` which were not written since the last
+/// time the directory was pruned, returning their paths.
+///
+/// Files are recognized as generated by ts-rs by their header, see [`Config::header`]. Directories which
+/// become empty are deleted as well.
+/// Files which were written contain the declarations of types exported into them before as well,
+/// since the bindings of every type are merged into the existing file. Declarations of types which
+/// were not exported since the last time the directory was pruned are removed from them.
+/// Afterwards, the manifest is reset, so that the next prune only keeps the bindings exported
+/// until then.
+/// If no bindings were exported into `out_dir` since it was last pruned, nothing is deleted.
+pub fn prune(out_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, ExportError> {
+    let mut manifest = Manifest::lock(out_dir.as_ref())?;
+    let Some(written) = manifest.files.take() else {
+        return Ok(vec![]);
+    };
+
+    let mut pruned = vec![];
+    prune_dir(&manifest.out_dir, Path::new(""), &written, &mut pruned)?;
+    manifest.write()?;
+
+    Ok(pruned)
+}
+
+fn prune_dir(
+    out_dir: &Path,
+    dir: &Path,
+    written: &BTreeMap<PathBuf, BTreeSet<String>>,
+    pruned: &mut Vec<PathBuf>,
+) -> Result<(), ExportError> {
+    for entry in std::fs::read_dir(out_dir.join(dir))? {
+        let entry = entry?;
+        let path = dir.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            let before = pruned.len();
+            prune_dir(out_dir, &path, written, pruned)?;
+            if pruned.len() > before && std::fs::read_dir(entry.path())?.next().is_none() {
+                std::fs::remove_dir(entry.path())?;
+            }
+        } else if let Some(declared) = written.get(&path) {
+            remove_stale_declarations(&entry.path(), &path, declared)?;
+        } else if is_generated(&entry.path())? {
+            std::fs::remove_file(entry.path())?;
+            pruned.push(out_dir.join(path));
+        }
+    }
+    Ok(())
+}
+
+/// Removes the declarations of all types but `declared` from the generated file at `file`, which
+/// is located at `path` within the export directory. Files written without declaring a type are
+/// left as they are.
+fn remove_stale_declarations(
+    file: &Path,
+    path: &Path,
+    declared: &BTreeSet<String>,
+) -> Result<(), ExportError> {
+    if declared.is_empty() {
+        return Ok(());
+    }
+    let Some(contents) = read_generated(file)? else {
+        return Ok(());
+    };
+
+    let config = Config::current()?;
+    let body = header::strip(config, &contents).unwrap_or(&contents);
+    if let Some(body) = retain(path, body, declared) {
+        write_atomic(file, &header::stamp(config, path, &body))?;
+    }
+    Ok(())
+}
+
+/// Removes the declarations of all types but `declared` from the body of the file at `path`, or
+/// their re-exports if it's a barrel, returning `None` if there are none to remove.
+pub(crate) fn retain(path: &Path, body: &str, declared: &BTreeSet<String>) -> Option<String> {
+    match barrel::is_index(path) {
+        true => barrel::retain(body, declared),
+        false => merge::retain(body, declared),
+    }
+}
+
+fn is_generated(path: &Path) -> Result<bool, ExportError> {
+    Ok(read_generated(path)?.is_some())
+}
+
+/// Returns the contents of the file at `path` if it was generated by ts-rs
+fn read_generated(path: &Path) -> Result<Option<String>, ExportError> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) if Config::current()?.is_generated(&contents) => Ok(Some(contents)),
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == ErrorKind::InvalidData => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
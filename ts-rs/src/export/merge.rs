@@ -0,0 +1,199 @@
+//! Merging the bindings of multiple types which are exported to the same file.
+//!
+//! The body of a file, without its header, is modeled as its imports, followed by named
+//! declaration blocks. Blocks are ordered by the type they belong to and by their kind, so the merged output
+//! does not depend on the order in which the types were exported.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Inserts the bindings of the type `ident` into the body of a file, replacing its previous
+/// declarations.
+pub(crate) fn upsert(contents: &str, ident: &str, generated: &str) -> String {
+    let mut bindings = Bindings::parse(contents);
+    let generated_bindings = Bindings::parse(generated);
+
+    let replaced = |key: &Key| key.owner == ident || generated_bindings.blocks.contains_key(key);
+
+    // if the file only contains this type, it's replaced entirely
+    if bindings.blocks.keys().all(replaced) {
+        return generated.to_owned();
+    }
+
+    bindings.blocks.retain(|key, _| !replaced(key));
+    bindings.blocks.extend(generated_bindings.blocks);
+    bindings.imports.extend(generated_bindings.imports);
+
+    bindings.to_string()
+}
+
+/// Removes the declarations of all types but `declared` from the body of a file, returning `None`
+/// if there are none to remove.
+pub(crate) fn retain(contents: &str, declared: &BTreeSet<String>) -> Option<String> {
+    let mut bindings = Bindings::parse(contents);
+    let before = bindings.blocks.len();
+    bindings
+        .blocks
+        .retain(|key, _| key.owner.is_empty() || declared.contains(&key.owner));
+
+    (bindings.blocks.len() != before).then(|| bindings.to_string())
+}
+
+/// The kind of a declaration block. Blocks of the same type are ordered by their kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    /// `export type` or `export interface`, including its docs and the constructor of branded
+    /// types
+    Type,
+    /// `export const XSchema`, the bincode schema of a type, or its declaration
+    Schema,
+    /// Any other exported value, e.g a codec
+    Value,
+}
+
+/// Identifies a declaration block within a file
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Key {
+    /// The type the block belongs to
+    owner: String,
+    kind: Kind,
+    /// The name of the declared type or value
+    name: String,
+}
+
+/// The contents of a file generated by ts-rs
+struct Bindings<'a> {
+    imports: BTreeSet<String>,
+    blocks: BTreeMap<Key, &'a str>,
+}
+
+impl<'a> Bindings<'a> {
+    fn parse(contents: &'a str) -> Self {
+        let mut imports = BTreeSet::new();
+        let mut blocks = BTreeMap::new();
+
+        let mut import: Option<String> = None;
+        let mut block_start: Option<usize> = None;
+        let mut previous_blank = true;
+        let mut offset = 0;
+
+        for line in contents.split_inclusive('\n') {
+            let trimmed = line.trim_end();
+            // blocks start at a top level declaration or doc comment following an empty line,
+            // so declarations may contain empty lines themselves
+            let starts_block = (previous_blank || block_start.is_none())
+                && (trimmed.starts_with("export ") || trimmed.starts_with("/**"));
+
+            if starts_block {
+                if let Some(start) = block_start {
+                    insert_block(&mut blocks, &contents[start..offset]);
+                }
+                block_start = Some(offset);
+            } else if block_start.is_none() {
+                // imports may span multiple lines once formatted
+                if let Some(import) = import.as_mut() {
+                    import.push('\n');
+                    import.push_str(trimmed);
+                } else if trimmed.starts_with("import ") {
+                    import = Some(trimmed.to_owned());
+                }
+                if import.as_ref().map_or(false, |i| i.ends_with(';')) {
+                    imports.extend(import.take());
+                }
+            }
+
+            previous_blank = trimmed.is_empty();
+            offset += line.len();
+        }
+
+        if let Some(start) = block_start {
+            insert_block(&mut blocks, &contents[start..]);
+        }
+
+        Self { imports, blocks }
+    }
+}
+
+fn insert_block<'a>(blocks: &mut BTreeMap<Key, &'a str>, block: &'a str) {
+    let block = block.trim_end();
+    if !block.is_empty() {
+        blocks.entry(key(block)).or_insert(block);
+    }
+}
+
+impl std::fmt::Display for Bindings<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // imports which were only used by previous declarations are dropped
+        let used = |import: &&String| {
+            imported_names(import).any(|name| self.blocks.values().any(|b| uses(b, name)))
+        };
+        for import in self.imports.iter().filter(used) {
+            writeln!(f, "{}", import)?;
+        }
+
+        for block in self.blocks.values() {
+            write!(f, "\n{}\n", block)?;
+        }
+        Ok(())
+    }
+}
+
+/// Determines the key of a declaration block from its first top level `export`
+fn key(block: &str) -> Key {
+    let Some(declaration) = block.lines().find_map(|line| line.strip_prefix("export ")) else {
+        return Key {
+            owner: String::new(),
+            kind: Kind::Value,
+            name: block.to_owned(),
+        };
+    };
+
+    // declarations of values within `.d.ts` files
+    let declaration = declaration.strip_prefix("declare ").unwrap_or(declaration);
+    let mut words = declaration.split_whitespace();
+    let keyword = words.next().unwrap_or_default();
+    let name = words
+        .next()
+        .unwrap_or_default()
+        .split(|c: char| !is_ident_char(c))
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+
+    let (owner, kind) = match keyword {
+        "type" | "interface" => (name.clone(), Kind::Type),
+        "const" => match name.strip_suffix("Schema") {
+            Some(owner) if !owner.is_empty() => (owner.to_owned(), Kind::Schema),
+            _ => (name.clone(), Kind::Value),
+        },
+        _ => (name.clone(), Kind::Value),
+    };
+
+    Key { owner, kind, name }
+}
+
+/// Returns the names imported by an import statement
+fn imported_names(import: &str) -> impl Iterator<Item = &str> {
+    let names = import
+        .split_once('{')
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map_or("", |(names, _)| names);
+
+    names
+        .split(',')
+        .map(|name| name.trim())
+        .map(|name| name.rsplit(" as ").next().unwrap_or(name))
+        .filter(|name| !name.is_empty())
+}
+
+/// Returns true if `block` refers to the identifier `name`
+fn uses(block: &str, name: &str) -> bool {
+    block.match_indices(name).any(|(index, _)| {
+        let before = block[..index].chars().next_back();
+        let after = block[index + name.len()..].chars().next();
+        !before.map_or(false, is_ident_char) && !after.map_or(false, is_ident_char)
+    })
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
@@ -1,6 +1,13 @@
-use std::path::{Component as C, Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Component as C, Path, PathBuf},
+    sync::Mutex,
+};
+
+use lazy_static::lazy_static;
 
 use super::ExportError as E;
+use crate::{Config, FileNameCase};
 
 const ERROR_MESSAGE: &str = r#"The path provided with `#[ts(export_to = "..")]` is not valid"#;
 
@@ -25,6 +32,63 @@ pub fn absolute<T: AsRef<Path>>(path: T) -> Result<PathBuf, E> {
     })
 }
 
+/// Returns the name of the file the type `ts_name` is exported to, following
+/// [`Config::file_names`] of the crate in `crate_dir`
+fn file_name(crate_dir: Option<&'static str>, ts_name: &str) -> String {
+    let case = Config::of_crate(crate_dir).map_or(FileNameCase::None, |config| config.file_names);
+    format!("{}.ts", case.apply(ts_name))
+}
+
+/// Output paths by the directory of the crate declaring the type, the directory within the export
+/// directory and the name of the type
+type Paths = HashMap<(Option<&'static str>, &'static str, &'static str), &'static Path>;
+
+/// Returns the path of the file the type `ts_name`, declared by the crate in `crate_dir`, is
+/// exported to within `dir`, which is either empty or a directory given by
+/// `#[ts(export_to = "dir/")]`.
+pub fn type_output_path(
+    crate_dir: Option<&'static str>,
+    dir: &'static str,
+    ts_name: &'static str,
+) -> &'static Path {
+    lazy_static! {
+        static ref PATHS: Mutex<Paths> = Mutex::new(HashMap::new());
+    }
+
+    let mut paths = PATHS.lock().unwrap_or_else(|e| e.into_inner());
+    paths.entry((crate_dir, dir, ts_name)).or_insert_with(|| {
+        let path = Path::new(dir).join(file_name(crate_dir, ts_name));
+        // leaked once for every type, so it can be returned from `TS::output_path`
+        Box::leak(path.into_boxed_path())
+    })
+}
+
+/// Returns the path of the file the type `ts_name` is exported to within the directories
+/// mirroring `module_path`, e.g `my_crate/api/User.ts` for a type declared in `my_crate::api`.
+#[cfg(feature = "module-paths")]
+pub fn module_output_path(
+    crate_dir: Option<&'static str>,
+    module_path: &'static str,
+    ts_name: &'static str,
+) -> &'static Path {
+    lazy_static! {
+        static ref PATHS: Mutex<Paths> = Mutex::new(HashMap::new());
+    }
+
+    let mut paths = PATHS.lock().unwrap_or_else(|e| e.into_inner());
+    paths
+        .entry((crate_dir, module_path, ts_name))
+        .or_insert_with(|| {
+            let path = module_path
+                .split("::")
+                .map(|module| module.trim_start_matches("r#"))
+                .map(str::to_owned)
+                .chain([file_name(crate_dir, ts_name)])
+                .collect::<PathBuf>();
+            Box::leak(path.into_boxed_path())
+        })
+}
+
 // Construct a relative path from a provided base directory path to the provided path.
 //
 // Copyright 2012-2015 The Rust Project Developers.
@@ -0,0 +1,231 @@
+//! A lightweight pretty-printer for the generated bindings, which is used instead of dprint if
+//! the `format` feature is disabled.
+//!
+//! Unlike dprint, it doesn't parse the bindings. Lines are re-indented according to the brackets
+//! they open and close, and lines which are too long are broken at the commas within their
+//! outermost brackets, e.g the fields of an object type.
+
+use std::collections::VecDeque;
+
+use crate::{Format, Quotes};
+
+/// Pretty-prints `text` according to `format`
+pub(crate) fn pretty_print(text: &str, format: &Format) -> String {
+    let mut lines = text.lines().map(str::to_owned).collect::<VecDeque<_>>();
+    let mut out = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    let mut in_comment = false;
+
+    while let Some(line) = lines.pop_front() {
+        let line = line.trim();
+        if line.is_empty() {
+            out.push('\n');
+            continue;
+        }
+
+        // lines within doc comments are aligned with the `*` of their first line
+        if in_comment || line.starts_with("/*") {
+            in_comment = !line.contains("*/");
+            out.push_str(&" ".repeat(depth * format.indent_width as usize));
+            if line.starts_with('*') {
+                out.push(' ');
+            }
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        let line = normalize(line, format);
+        let closing = line
+            .chars()
+            .take_while(|c| matches!(c, '}' | ']' | ')'))
+            .count();
+        let indent = " ".repeat(depth.saturating_sub(closing) * format.indent_width as usize);
+
+        if indent.len() + line.chars().count() > format.line_width as usize {
+            if let Some(broken) = break_line(&line) {
+                for line in broken.into_iter().rev() {
+                    lines.push_front(line);
+                }
+                continue;
+            }
+        }
+
+        out.push_str(&indent);
+        out.push_str(&line);
+        out.push('\n');
+        depth = (depth as isize + nesting(&line)).max(0) as usize;
+    }
+    out
+}
+
+/// A character of a line, and whether it is part of code, a string literal or a comment
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Char {
+    Code(char),
+    /// A quote starting or ending a string literal
+    Quote(char),
+    Literal(char),
+    Comment(char),
+}
+
+/// Classifies the characters of `line`
+fn chars(line: &str) -> Vec<Char> {
+    let mut chars = Vec::with_capacity(line.len());
+    let mut quote = None;
+    let mut escaped = false;
+    let mut iter = line.chars().peekable();
+
+    while let Some(c) = iter.next() {
+        match quote {
+            Some(q) if !escaped && c == q => {
+                quote = None;
+                chars.push(Char::Quote(c));
+            }
+            Some(_) => {
+                escaped = !escaped && c == '\\';
+                chars.push(Char::Literal(c));
+            }
+            None if c == '/' && iter.peek() == Some(&'/') => {
+                chars.push(Char::Comment(c));
+                chars.extend(iter.by_ref().map(Char::Comment));
+            }
+            None if matches!(c, '"' | '\'' | '`') => {
+                quote = Some(c);
+                chars.push(Char::Quote(c));
+            }
+            None => chars.push(Char::Code(c)),
+        }
+    }
+    chars
+}
+
+/// Applies the configured quotes and semicolons to `line`
+fn normalize(line: &str, format: &Format) -> String {
+    let mut chars = chars(line);
+
+    if !format.semicolons {
+        let last = chars.iter().rposition(|c| !matches!(c, Char::Comment(_)));
+        if let Some(last) = last.filter(|&last| chars[last] == Char::Code(';')) {
+            chars.remove(last);
+        }
+    }
+
+    let mut out = String::with_capacity(line.len());
+    let mut current = None;
+    for c in chars {
+        match c {
+            Char::Quote(q) if format.quotes == Quotes::Single && q == '"' => {
+                current = current.xor(Some(q));
+                out.push('\'');
+            }
+            Char::Quote(q) => {
+                current = current.xor(Some(q));
+                out.push(q);
+            }
+            Char::Literal('\'') if current == Some('"') && format.quotes == Quotes::Single => {
+                out.push_str("\\'");
+            }
+            Char::Literal(c) => out.push(c),
+            Char::Code(c) | Char::Comment(c) => out.push(c),
+        }
+    }
+
+    // the escaped double quotes within string literals which now use single quotes
+    match format.quotes {
+        Quotes::Single => unescape_double_quotes(&out),
+        Quotes::Double => out,
+    }
+}
+
+/// Replaces `\"` within single quoted string literals by `"`
+fn unescape_double_quotes(line: &str) -> String {
+    let chars = chars(line);
+    let mut out = String::with_capacity(line.len());
+    let mut quote = None;
+    let mut iter = chars.iter().peekable();
+    while let Some(&c) = iter.next() {
+        match c {
+            Char::Quote(q) => {
+                quote = quote.xor(Some(q));
+                out.push(q);
+            }
+            Char::Literal('\\')
+                if quote == Some('\'') && iter.peek() == Some(&&Char::Literal('"')) => {}
+            Char::Code(c) | Char::Literal(c) | Char::Comment(c) => out.push(c),
+        }
+    }
+    out
+}
+
+/// Returns how many brackets `line` opens, minus how many it closes
+fn nesting(line: &str) -> isize {
+    chars(line)
+        .into_iter()
+        .map(|c| match c {
+            Char::Code('{' | '[' | '(') => 1,
+            Char::Code('}' | ']' | ')') => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Breaks `line` at the commas within its first outermost brackets which contain any, putting
+/// every element on its own line.
+fn break_line(line: &str) -> Option<Vec<String>> {
+    let chars = chars(line);
+    let text = |range: &[Char]| {
+        range
+            .iter()
+            .map(|c| match *c {
+                Char::Code(c) | Char::Quote(c) | Char::Literal(c) | Char::Comment(c) => c,
+            })
+            .collect::<String>()
+    };
+
+    let mut depth = 0;
+    let mut angles = 0;
+    // the opening bracket, and how many angle brackets were open at that point
+    let mut open = None;
+    let mut commas = vec![];
+    for (i, c) in chars.iter().enumerate() {
+        let Char::Code(c) = *c else {
+            continue;
+        };
+        match c {
+            '{' | '[' if depth == 0 => {
+                open = Some((i, angles));
+                commas.clear();
+                depth += 1;
+            }
+            '{' | '[' | '(' => depth += 1,
+            '<' => angles += 1,
+            // the arrow of a function type is not a closing bracket
+            '>' if i > 0 && chars[i - 1] == Char::Code('=') => {}
+            '>' => angles -= 1,
+            ',' if depth == 1 && open.map_or(false, |(_, a)| a == angles) => commas.push(i),
+            '}' | ']' if depth == 1 && open.is_some() => {
+                depth -= 1;
+                let (open, _) = open.take()?;
+                if commas.is_empty() {
+                    continue;
+                }
+
+                let mut lines = vec![text(&chars[..=open])];
+                let mut start = open + 1;
+                for &end in commas.iter().chain(Some(&i)) {
+                    let element = text(&chars[start..end]);
+                    if !element.trim().is_empty() {
+                        lines.push(format!("{},", element.trim()));
+                    }
+                    start = end + 1;
+                }
+                lines.push(text(&chars[i..]));
+                return Some(lines);
+            }
+            '}' | ']' | ')' => depth = (depth - 1).max(0),
+            _ => (),
+        }
+    }
+    None
+}
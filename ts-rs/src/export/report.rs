@@ -0,0 +1,224 @@
+//! A summary of what happened while exporting, see [`TS::export_all_with_report`](crate::TS::export_all_with_report).
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter, Write as _},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use super::{barrel::Entry, header::fnv1a, ExportError, ExportSink};
+
+/// What happened while exporting types and their dependencies.
+///
+/// The report can be rendered as JSON using [`ExportReport::to_json`], e.g to annotate a pull
+/// request with the bindings it changed:
+/// ```no_run
+/// # use ts_rs::{Directory, TS};
+/// # #[derive(TS)]
+/// # struct User;
+/// let report = User::export_all_with_report(&mut Directory::new("bindings")).unwrap();
+/// for path in report.changed() {
+///     println!("bindings changed: {}", path.display());
+/// }
+/// std::fs::write("ts-rs-report.json", report.to_json()).unwrap();
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct ExportReport {
+    /// Every file which was exported, by its path within the sink
+    pub files: BTreeMap<PathBuf, FileReport>,
+    /// Dependencies which were not exported because they can't be, e.g primitives or types from
+    /// other crates, by the name of their Rust type
+    pub skipped: BTreeSet<&'static str>,
+    /// Time spent exporting
+    pub duration: Duration,
+}
+
+/// What happened to a single file while exporting
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct FileReport {
+    /// Whether the file was created, updated or left as it was
+    pub status: FileStatus,
+    /// The TypeScript types declared within the file by this export
+    pub types: Vec<String>,
+    /// The schema constants emitted into the file by this export
+    pub schemas: Vec<String>,
+    /// Time spent generating, formatting and writing the file
+    pub duration: Duration,
+    /// Hash of the file before it was first written to
+    previous: Option<u64>,
+    /// Hash of the file as it is now
+    current: Option<u64>,
+}
+
+/// Whether an exported file changed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The file did not exist before
+    Created,
+    /// The contents of the file changed
+    Updated,
+    /// The file was exported, but its contents stayed the same
+    Unchanged,
+}
+
+impl ExportReport {
+    /// Returns the paths of all files which were created or updated
+    pub fn changed(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(|(_, file)| file.status != FileStatus::Unchanged)
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// Renders the report as JSON.
+    ///
+    /// ```json
+    /// {
+    ///   "files": [
+    ///     {
+    ///       "path": "User.ts",
+    ///       "status": "created",
+    ///       "types": ["User"],
+    ///       "schemas": ["UserSchema"],
+    ///       "duration_ms": 0.412
+    ///     }
+    ///   ],
+    ///   "skipped": ["alloc::string::String"],
+    ///   "duration_ms": 0.63
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\n  \"files\": [");
+        for (i, (path, file)) in self.files.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            let path = path.to_string_lossy().replace('\\', "/");
+            // writing into a `String` never fails
+            let _ = write!(
+                out,
+                "{}\n    {{\n      \"path\": {},\n      \"status\": \"{}\",\n      \"types\": {},\n      \"schemas\": {},\n      \"duration_ms\": {}\n    }}",
+                separator,
+                json_string(&path),
+                file.status,
+                json_array(&file.types),
+                json_array(&file.schemas),
+                millis(file.duration),
+            );
+        }
+        if !self.files.is_empty() {
+            out.push_str("\n  ");
+        }
+        let _ = write!(
+            out,
+            "],\n  \"skipped\": {},\n  \"duration_ms\": {}\n}}\n",
+            json_array(&self.skipped),
+            millis(self.duration),
+        );
+        out
+    }
+
+    /// Writes `contents` to `path` within `sink`, recording whether the file changed.
+    /// If the file already has these contents, it's not written again.
+    ///
+    /// This is how every exported file is written, and can be used to write bindings which were
+    /// exported elsewhere, e.g by another process, into a sink.
+    pub fn write(
+        &mut self,
+        sink: &mut (impl ExportSink + ?Sized),
+        path: &Path,
+        contents: String,
+    ) -> Result<(), ExportError> {
+        if !self.files.contains_key(path) {
+            let previous = sink.read(path)?;
+            let file = FileReport::new(previous.as_deref().map(fnv1a));
+            self.files.insert(path.to_owned(), file);
+        }
+
+        if let Some(file) = self.files.get_mut(path) {
+            let hash = fnv1a(&contents);
+            file.status = match file.previous {
+                None => FileStatus::Created,
+                Some(previous) if previous == hash => FileStatus::Unchanged,
+                Some(_) => FileStatus::Updated,
+            };
+            if file.current == Some(hash) {
+                return Ok(());
+            }
+            file.current = Some(hash);
+        }
+        sink.write(path, contents)
+    }
+
+    /// Records that the type of `entry` was exported to `path`, which took `duration`.
+    ///
+    /// In check mode, nothing is written, so files which are up to date are recorded here.
+    pub(crate) fn record(&mut self, path: &Path, entry: &Entry, duration: Duration) {
+        let file = self
+            .files
+            .entry(path.to_owned())
+            .or_insert_with(|| FileReport::new(None));
+
+        if !file.types.contains(&entry.ident) {
+            file.types.push(entry.ident.clone());
+        }
+        if let Some(schema) = entry.schema.as_ref().filter(|s| !file.schemas.contains(s)) {
+            file.schemas.push(schema.clone());
+        }
+        file.duration += duration;
+    }
+}
+
+impl FileReport {
+    fn new(previous: Option<u64>) -> Self {
+        Self {
+            status: FileStatus::Unchanged,
+            types: vec![],
+            schemas: vec![],
+            duration: Duration::ZERO,
+            previous,
+            current: previous,
+        }
+    }
+}
+
+impl Display for FileStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FileStatus::Created => "created",
+            FileStatus::Updated => "updated",
+            FileStatus::Unchanged => "unchanged",
+        })
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_array<T: AsRef<str>>(values: impl IntoIterator<Item = T>) -> String {
+    let values = values
+        .into_iter()
+        .map(|value| json_string(value.as_ref()))
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,132 @@
+//! Sinks, which receive the files generated when exporting types.
+
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::{manifest::Manifest, path, write_atomic, ExportError};
+
+/// A destination for exported bindings, e.g a directory on disk or an in-memory map of files.
+///
+/// All paths are relative to the root of the sink, e.g `api/User.ts`.
+/// When multiple types are exported into the same file, the file is read back and the new
+/// declarations are merged into it.
+///
+/// ```
+/// # use std::{collections::BTreeMap, path::PathBuf};
+/// # use ts_rs::TS;
+/// #[derive(TS)]
+/// struct User {
+///     id: UserId,
+/// }
+///
+/// #[derive(TS)]
+/// struct UserId(u64);
+///
+/// let mut files = BTreeMap::<PathBuf, String>::new();
+/// User::export_all_into(&mut files).unwrap();
+/// assert!(files.contains_key(&PathBuf::from("UserId.ts")));
+/// ```
+pub trait ExportSink {
+    /// Returns the contents of the file at `path` if it was written to this sink before.
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError>;
+
+    /// Creates the file at `path`, replacing it if it already exists.
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError>;
+
+    /// Returns the location of the file at `path` on disk, if the sink writes to the filesystem.
+    ///
+    /// Files on disk are checked instead of written if `TS_RS_CHECK` is set, and the types
+    /// declared within them are tracked across exports.
+    fn path_on_disk(&self, path: &Path) -> Option<PathBuf> {
+        let _ = path;
+        None
+    }
+
+    /// Called once the bindings of the type `ident` were written to the file at `path`.
+    ///
+    /// [`Directory`] records the types declared within every file, so that
+    /// [`prune`](crate::prune) can remove the declarations of types which are no longer exported.
+    fn declared(&mut self, path: &Path, ident: &str) -> Result<(), ExportError> {
+        let _ = (path, ident);
+        Ok(())
+    }
+}
+
+/// Keeps the generated files in memory
+impl ExportSink for BTreeMap<PathBuf, String> {
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        Ok(self.get(path).cloned())
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        self.insert(path.to_owned(), contents);
+        Ok(())
+    }
+}
+
+/// Writes bindings into a directory on disk, which is what [`TS::export_all_to`](crate::TS::export_all_to)
+/// does.
+///
+/// Every file, and the types declared within it, is recorded in the manifest of the directory, see
+/// [`prune`](crate::prune).
+/// The directory is locked from the first time a file is read or written until the `Directory`
+/// is dropped, so that other threads and processes exporting into it wait until we're done.
+pub struct Directory {
+    out_dir: PathBuf,
+    manifest: Option<Manifest>,
+}
+
+impl Directory {
+    /// Creates a sink writing into `out_dir`, which is created if it doesn't exist.
+    pub fn new(out_dir: impl AsRef<Path>) -> Self {
+        Self {
+            out_dir: out_dir.as_ref().to_owned(),
+            manifest: None,
+        }
+    }
+
+    fn manifest(&mut self) -> Result<&mut Manifest, ExportError> {
+        if self.manifest.is_none() {
+            self.manifest = Some(Manifest::lock(&self.out_dir)?);
+        }
+        Ok(self.manifest.as_mut().unwrap())
+    }
+}
+
+impl ExportSink for Directory {
+    /// Returns the contents of the file, which the bindings of other types are merged into
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        let file = path::absolute(self.out_dir.join(path))?;
+        // other processes may only write the file once we're done with it
+        self.manifest()?;
+
+        match std::fs::read_to_string(&file) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        let file = path::absolute(self.out_dir.join(path))?;
+        let manifest = self.manifest()?;
+
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_atomic(&file, &contents)?;
+        manifest.record(&file, None)
+    }
+
+    fn declared(&mut self, path: &Path, ident: &str) -> Result<(), ExportError> {
+        let file = path::absolute(self.out_dir.join(path))?;
+        self.manifest()?.record(&file, Some(ident))
+    }
+
+    fn path_on_disk(&self, path: &Path) -> Option<PathBuf> {
+        Some(self.out_dir.join(path))
+    }
+}
@@ -55,6 +55,27 @@
 //! }
 //! ```
 //! When running `cargo test`, the TypeScript bindings will be exported to the file `bindings/User.ts`.
+//! To verify that the bindings on disk are up to date instead, e.g in CI, run `TS_RS_CHECK=1 cargo test`.
+//! Every exported file is recorded in `bindings/.ts-rs-manifest.json`. Calling `ts_rs::prune("./bindings")` afterwards deletes
+//! bindings which were generated by ts-rs, but are no longer exported, e.g because a type was renamed.
+//!
+//! With the `registry` feature enabled, all types annotated with `#[ts(export)]` can also be exported
+//! from a binary, an example or a build script using `ts_rs::export_registered("./bindings")`.
+//!
+//! To regenerate the bindings on every `cargo build` instead, export them from a build script using `ts_rs::build::Export`,
+//! either by running a binary of the crate with `Export::binary("export-bindings")`, or from a helper crate depending on it using
+//! `Export::registered()`. Only the build script reruns if the sources or the configuration of the crate change.
+//!
+//! To export multiple types together with all of their dependencies into a single, self-contained file instead,
+//! use `ts_rs::Bundle::new().add::<A>().add::<B>().write("protocol.ts")`.
+//!
+//! To obtain the bindings without touching the filesystem, export them into an `ExportSink`, e.g. an in-memory `BTreeMap<PathBuf, String>`
+//! using `User::export_all_into(&mut files)`. With the `tar-archive` and `zip-archive` features, bindings can be written into an archive as well.
+//! `User::export_all_with_report(&mut sink)` additionally returns an `ExportReport`, listing which files were created, updated or
+//! left unchanged, which can be rendered as JSON, e.g. to annotate a pull request with the bindings it changed.
+//!
+//! To export, check or clean bindings from the command line, use the `cargo ts-rs` subcommand found in `cli/`.
+//! `cargo ts-rs watch` exports them again whenever the sources change, only touching files whose contents changed.
 //!
 //! ## Features
 //! - generate type declarations from rust structs
@@ -66,6 +87,43 @@
 //! - generic types
 //! - support for ESM imports
 //!
+//! ## Configuration
+//! Export settings can be configured per crate in a `ts-rs.toml` next to its `Cargo.toml`, or in the `[package.metadata.ts-rs]` table
+//! of the manifest. Settings shared by a whole workspace go into a `ts-rs.toml` at its root, or into `[workspace.metadata.ts-rs]`.
+//! Files closer to the crate override the ones further up, so one crate can e.g. use ESM imports while another doesn't.
+//! The bindings of a type are generated as configured for the crate declaring it, even if another crate exports them. Only the
+//! export directory and the `index.ts` files of the `barrel` feature follow the crate being exported.
+//! ```toml
+//! base = "workspace"       # resolve TS_RS_EXPORT_DIR and ./bindings against "workspace", "crate" or a path
+//! out_dir = "bindings"     # relative to this file, overridden by TS_RS_EXPORT_DIR
+//! import_extension = "js"  # "none", "js" or "ts"
+//! output = "ts"            # "ts", or "js" for declarations in .d.ts and values in .js files
+//! file_names = "kebab-case" # "none", "PascalCase", "camelCase", "kebab-case" or "snake_case"
+//! bigint = "bigint"        # represent 64 and 128 bit integers as "bigint", "number" or "string"
+//! typed_arrays = true      # represent e.g. Vec<u8> as Uint8Array, or a single field using #[ts(typed_array)]
+//! readonly = true          # readonly properties and ReadonlyArray<T>, or a single type using #[ts(readonly)]
+//! interface = true         # declare structs as interfaces, or a single struct using #[ts(interface)]
+//! es_collections = true    # Map<K, V> and Set<T> instead of objects and arrays, or a single field using #[ts(es_collections)]
+//!
+//! [format]                 # or `format = true` for the defaults, every setting is optional
+//! formatter = "dprint"     # "dprint" (requires the `format` feature) or "builtin"
+//! indent_width = 4
+//! line_width = 100
+//! quotes = "single"        # "double" or "single"
+//! semicolons = false
+//! dprint_config = "dprint.json"  # relative to this file, replaces the settings above
+//! ```
+//! The environment variables `TS_RS_IMPORT_EXTENSION` (e.g. `js`) and `TS_RS_FORMAT` (`true` or `false`) override the
+//! import extension and formatting of every crate.
+//! The configuration is read at export time, so changing it does not require recompiling. Setting `base` makes the
+//! output directory independent of the working directory, so all crates of a workspace export into the same directory and
+//! imports between them resolve.
+//! The `header` setting replaces the comment at the top of every generated file, e.g. with a license, `/* eslint-disable */`
+//! or `// @ts-nocheck`. It may contain the placeholders `{version}`, `{hash}` (of the rest of the file) and `{file}`.
+//! Without the `format` feature, the bindings can still be formatted by a built-in pretty-printer, which re-indents them
+//! and breaks long lines without pulling in any dependencies. Projects already using dprint can point `dprint_config`
+//! to their `dprint.json`, so the bindings are formatted like the rest of their code.
+//!
 //! ## cargo features
 //! | **Feature**        | **Description**                                                                                                                                                                                           |
 //! |:-------------------|-----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
@@ -86,6 +144,12 @@
 //! | semver-impl        | Implement `TS` for types from *semver*                                                                                                                                                                    |
 //! | smol_str-impl      | Implement `TS` for types from *smol_str*                                                                                                                                                                    |
 //! | tokio-impl         | Implement `TS` for types from *tokio*                                                                                                                                                                    |
+//! | barrel             | Generate an `index.ts` in every directory bindings are exported to, re-exporting all of its types and schemas, e.g. `export type { User } from "./User"`.                                                   |
+//! | import-aliases     | Import types whose names collide with another type under an alias prefixed by their module, e.g. `import type { Error as B_Error }`. <br/>Without it, exporting such a type fails with `ExportError::NameCollision`. |
+//! | module-paths       | Export types to directories mirroring their Rust module, e.g. `bindings/my_crate/api/User.ts` for `my_crate::api::User`. <br/>Types with `#[ts(export_to = "..")]` are exported to the given path instead. <br/>Enables `import-aliases`, since types with the same name may now reference each other. |
+//! | registry           | Register every type annotated with `#[ts(export)]`, so that all of them can be exported without `cargo test` using `ts_rs::export_registered`. <br/>Requires Rust 1.68.                                |
+//! | tar-archive        | Add `ts_rs::TarArchive`, a sink writing bindings into a tar archive, see `TS::export_all_into`.                                                                                                           |
+//! | zip-archive        | Add `ts_rs::ZipArchive`, a sink writing bindings into a zip archive, see `TS::export_all_into`.                                                                                                           |
 //!
 //! <br/>
 //!
@@ -132,11 +196,58 @@ use std::{
 
 pub use ts_rs_macros::TS;
 
-pub use crate::export::ExportError;
+pub use crate::config::{
+    BigIntStyle, Config, FileNameCase, Format, Formatter, ImportExtension, Output, Quotes,
+};
+pub use crate::export::{
+    prune, Bundle, Directory, ExportError, ExportReport, ExportSink, FileReport, FileStatus,
+};
+#[cfg(feature = "tar-archive")]
+pub use crate::export::TarArchive;
+#[cfg(feature = "zip-archive")]
+pub use crate::export::ZipArchive;
+#[cfg(feature = "module-paths")]
+#[doc(hidden)]
+pub use crate::export::module_output_path;
+#[doc(hidden)]
+pub use crate::export::type_output_path;
+#[doc(hidden)]
+pub use crate::render::{aliased, branded, RenderOptions};
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory;
+#[cfg(feature = "registry")]
+pub use crate::registry::{
+    export_registered, export_registered_into, export_registered_with_report, registered,
+    Registration,
+};
+
+/// Submits the registration of a type annotated with `#[ts(export)]` to the registry, or does
+/// nothing if the `registry` feature is disabled.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($registration:expr) => {
+        $crate::inventory::submit! { $registration }
+    };
+}
+
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($registration:expr) => {};
+}
 
+pub mod build;
 #[cfg(feature = "chrono-impl")]
 mod chrono;
+mod config;
 mod export;
+#[cfg(feature = "registry")]
+mod registry;
+mod render;
 #[cfg(feature = "serde-json-impl")]
 mod serde_json;
 #[cfg(feature = "tokio-impl")]
@@ -194,7 +305,8 @@ mod tokio;
 ///   <br/>
 ///
 /// - **`#[ts(export_to = "..")]`**  
-///   Specifies where the type should be exported to. Defaults to `<name>.ts`.  
+///   Specifies where the type should be exported to. Defaults to `<name>.ts`, using the case configured by `file_names`
+///   in `ts-rs.toml`.  
 ///   The path given to the `export_to` attribute is relative to the `TS_RS_EXPORT_DIR` environment variable,
 ///   or, if `TS_RS_EXPORT_DIR` is not set, to `./bindings`  
 ///   If the provided path ends in a trailing `/`, it is interpreted as a directory.   
@@ -275,11 +387,36 @@ mod tokio;
 ///   ```
 ///   <br/><br/>
 ///
+/// - **`#[ts(readonly)]`**  
+///   Generates an immutable type: Every property is marked as `readonly`, sequences like `Vec<T>`
+///   are represented as `ReadonlyArray<T>` and tuples as `readonly [..]`.  
+///   This also applies to types inlined into this one, but not to types referenced by name.  
+///   To do this for all types, set `readonly = true` in the configuration. A single type can
+///   then opt out using `#[ts(readonly = false)]`.
+///   <br/><br/>
+///
 /// ### struct attributes
 /// - **`#[ts(tag = "..")]`**  
 ///   Include the structs name (or value of `#[ts(rename = "..")]`) as a field with the given key.
 ///   <br/><br/>
 ///
+/// - **`#[ts(interface)]`**  
+///   Declare the struct as an `interface` instead of a type alias, e.g.
+///   `export interface User { id: number, name: string, }`.  
+///   Flattened fields become `extends` clauses. If a flattened field is not an object type, e.g.
+///   an enum, an interface cannot extend it, so a type alias is declared instead.  
+///   To do this for all structs with named fields, set `interface = true` in the configuration.
+///   A single struct can then opt out using `#[ts(interface = false)]`.
+///   <br/><br/>
+///
+/// - **`#[ts(brand)]`**  
+///   Brand a newtype, so it cannot be confused with other types sharing the same representation,
+///   e.g. `struct UserId(u64)` becomes `type UserId = bigint & { readonly __brand: "UserId" }`.  
+///   A constructor is exported alongside the type:
+///   `export const UserId = (value: bigint): UserId => value as UserId;`.  
+///   The schema of a branded type is the same as the one of a newtype without a brand.
+///   <br/><br/>
+///
 /// ### struct field attributes
 ///
 /// - **`#[ts(type = "..")]`**  
@@ -314,6 +451,21 @@ mod tokio;
 /// - **`#[ts(flatten)]`**  
 ///   Flatten this field, inlining all the keys of the field's type into its parent.
 ///   <br/><br/>
+///
+/// - **`#[ts(typed_array)]`**  
+///   Represent this field as a JavaScript typed array, e.g. `Vec<u8>` as `Uint8Array` or
+///   `[f32; 3]` as `Float32Array`. The field's schema is marked with `"typedArray"`, so decoders can
+///   create a view into the decoded buffer instead of reading every element.  
+///   For element types wider than a byte, this requires bincode's fixed-width integer encoding.  
+///   To do this for all fields, set `typed_arrays = true` in the configuration.
+///   <br/><br/>
+///
+/// - **`#[ts(es_collections)]`**  
+///   Represent the maps and sets in this field as ES `Map<K, V>` and `Set<T>` instead of objects and
+///   arrays. Unlike objects, these support keys which are not strings or numbers, like structs.
+///   The field's schema is marked with `"esCollections"`, so decoders build a `Map` or `Set`.  
+///   To do this for all fields, set `es_collections = true` in the configuration.
+///   <br/><br/>
 ///   
 /// ### enum attributes
 ///
@@ -388,6 +540,19 @@ pub trait TS {
     /// automatically read from your doc comments or `#[doc = ".."]` attributes
     const DOCS: Option<&'static str> = None;
 
+    /// Name of the JavaScript typed array a sequence of this type can be stored in, e.g.
+    /// `Uint8Array` for `u8`.
+    /// Sequences like `Vec<T>` or `[T; N]` are only represented as typed arrays if this is set and
+    /// typed arrays are enabled, either globally using the `typed_arrays` setting of the
+    /// configuration or for a single field using `#[ts(typed_array)]`.
+    const TYPED_ARRAY: Option<&'static str> = None;
+
+    /// Whether this type is represented as an object type with statically known members, e.g.
+    /// `{ a: number, b: string }`. This is the case for structs with named fields.
+    /// When a struct is declared as an interface, it can only `extends` the types of its flattened
+    /// fields if they are object types.
+    const OBJECT: bool = false;
+
     /// Identifier of this type, excluding generic parameters.
     fn ident() -> String {
         // by default, fall back to `TS::name()`.
@@ -413,6 +578,13 @@ pub trait TS {
     /// If this type is not generic, then this function is equivalent to `TS::decl()`.
     fn decl_concrete() -> String;
 
+    /// Declaration of a function creating this type from its representation, e.g.
+    /// `const UserId = (value: bigint): UserId => value as UserId;`.
+    /// This is only generated for branded newtypes, see `#[ts(brand)]`.
+    fn constructor() -> Option<String> {
+        None
+    }
+
     /// Name of this type in TypeScript, including generic parameters
     fn name() -> String;
 
@@ -431,7 +603,7 @@ pub trait TS {
     /// Name of the schema variable exported by this type's file.
     /// e.g. `"GenderSchema"` for a type named `Gender`.
     fn schema_var_name() -> String {
-        format!("{}Schema", Self::ident())
+        format!("{}Schema", render::aliased::<Self>(&Self::ident()))
     }
 
     /// Iterates over all types referenced in this type's schema definitions section.
@@ -515,11 +687,10 @@ pub trait TS {
     where
         Self: 'static,
     {
-        let path = Self::default_output_path()
-            .ok_or_else(std::any::type_name::<Self>)
-            .map_err(ExportError::CannotBeExported)?;
-
-        export::export_to::<Self, _>(path)
+        export::check::check_if_enabled(|| {
+            let mut sink = Directory::new(export::default_out_dir());
+            export::export_into::<Self>(&mut Default::default(), &mut sink)
+        })
     }
 
     /// Manually export this type to the filesystem, together with all of its dependencies.  
@@ -542,7 +713,10 @@ pub trait TS {
     where
         Self: 'static,
     {
-        export::export_all_into::<Self>(&*export::default_out_dir())
+        export::check::check_if_enabled(|| {
+            export::export_all_into::<Self>(&mut Directory::new(export::default_out_dir()))?;
+            Ok(())
+        })
     }
 
     /// Manually export this type into the given directory, together with all of its dependencies.  
@@ -562,7 +736,92 @@ pub trait TS {
     where
         Self: 'static,
     {
-        export::export_all_into::<Self>(out_dir)
+        export::check::check_if_enabled(|| {
+            export::export_all_into::<Self>(&mut Directory::new(out_dir))?;
+            Ok(())
+        })
+    }
+
+    /// Manually export this type into the given sink, together with all of its dependencies.
+    ///
+    /// Sinks receive every generated file by its path relative to the export directory, e.g
+    /// `api/User.ts`. This makes it possible to obtain the bindings without touching the
+    /// filesystem, e.g by exporting into a `BTreeMap<PathBuf, String>`:
+    /// ```
+    /// # use std::{collections::BTreeMap, path::PathBuf};
+    /// # use ts_rs::TS;
+    /// #[derive(TS)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// let mut files = BTreeMap::<PathBuf, String>::new();
+    /// User::export_all_into(&mut files).unwrap();
+    /// assert!(files[&PathBuf::from("User.ts")].contains("export type User"));
+    /// ```
+    /// Exporting into a [`Directory`] is equivalent to [`TS::export_all_to`]. With the
+    /// `tar-archive` and `zip-archive` features, bindings can be written into an archive as well.
+    fn export_all_into(sink: &mut (impl ExportSink + ?Sized)) -> Result<(), ExportError>
+    where
+        Self: 'static,
+    {
+        export::check::check_if_enabled(|| export::export_all_into::<Self>(sink))?;
+        Ok(())
+    }
+
+    /// Manually export this type into the given sink, together with all of its dependencies,
+    /// returning a report of what happened.
+    ///
+    /// The report lists every file which was created, updated or left unchanged, the types and
+    /// schemas within them, the dependencies which could not be exported, e.g primitives, and how
+    /// long exporting took. Using [`ExportReport::to_json`], it can be handed to other tools, e.g
+    /// to annotate a pull request with the bindings it changed.
+    /// ```
+    /// # use std::{collections::BTreeMap, path::PathBuf};
+    /// # use ts_rs::{FileStatus, TS};
+    /// #[derive(TS)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// let mut files = BTreeMap::<PathBuf, String>::new();
+    /// let report = User::export_all_with_report(&mut files).unwrap();
+    /// assert_eq!(report.files[&PathBuf::from("User.ts")].status, FileStatus::Created);
+    /// assert!(report.skipped.contains("alloc::string::String"));
+    /// ```
+    /// If `TS_RS_CHECK` is set, the bindings are checked instead, in which case every file in the
+    /// report is unchanged.
+    fn export_all_with_report(
+        sink: &mut (impl ExportSink + ?Sized),
+    ) -> Result<ExportReport, ExportError>
+    where
+        Self: 'static,
+    {
+        export::check::check_if_enabled(|| export::export_all_into::<Self>(sink))
+    }
+
+    /// Check whether the bindings of this type and all of its dependencies within the given
+    /// directory are up to date, without writing anything.
+    ///
+    /// The bindings are rendered exactly like [`TS::export_all_to`] would, and compared with the
+    /// files on disk. Files containing multiple types are checked by comparing the declarations
+    /// of each type individually. Declarations which [`prune`] would remove from them are
+    /// reported as well.  
+    /// If any file is out of date, [`ExportError::OutOfDate`] is returned, containing a unified
+    /// diff for every stale file.
+    ///
+    /// # Check Mode
+    /// Setting the `TS_RS_CHECK` environment variable turns [`TS::export`], [`TS::export_all`]
+    /// and [`TS::export_all_to`] into checks as well. Running `TS_RS_CHECK=1 cargo test` in CI
+    /// therefore fails if the bindings were not regenerated after a type changed.
+    fn check_all_in(out_dir: impl AsRef<Path>) -> Result<(), ExportError>
+    where
+        Self: 'static,
+    {
+        export::check::check(|| {
+            export::export_all_into::<Self>(&mut Directory::new(out_dir))?;
+            Ok(())
+        })
     }
 
     /// Manually generate bindings for this type, returning a [`String`].  
@@ -596,6 +855,13 @@ pub trait TS {
         None
     }
 
+    /// Returns the directory of the crate declaring `T`, whose configuration applies to its
+    /// bindings, or `None` if it wasn't derived.
+    #[doc(hidden)]
+    fn crate_dir() -> Option<&'static str> {
+        None
+    }
+
     /// Returns the output path to where `T` should be exported.  
     ///
     /// The output of this function depends on the environment variable `TS_RS_EXPORT_DIR`, which is
@@ -636,6 +902,9 @@ pub struct Dependency {
     pub output_path: &'static Path,
     /// Name of the schema variable exported by this type's file, e.g. `"GenderSchema"`.
     pub schema_var_name: String,
+    /// Path of the rust type, e.g. `"my_crate::api::Gender"`. Instances of a generic type share
+    /// the same path.
+    pub rust_path: &'static str,
 }
 
 impl Dependency {
@@ -649,31 +918,46 @@ impl Dependency {
             ts_name: T::ident(),
             output_path,
             schema_var_name: T::schema_var_name(),
+            rust_path: export::rust_path::<T>(),
         })
     }
 }
 
 // generate impls for primitive types
 macro_rules! impl_primitives {
-    ($($($ty:ty),* => $l:literal),*) => { $($(
+    (@impl $ty:ty => $l:literal, $typed_array:expr) => {
         impl TS for $ty {
             type WithoutGenerics = Self;
-            fn name() -> String { $l.to_owned() }
+            const TYPED_ARRAY: Option<&'static str> = $typed_array;
+            fn name() -> String { $crate::render::primitive($l).to_owned() }
             fn inline() -> String { <Self as $crate::TS>::name() }
             fn inline_flattened() -> String { panic!("{} cannot be flattened", <Self as $crate::TS>::name()) }
             fn schema(_: bool) -> String { format!("{{ \"type\" : \"{}\" }}", <Self as $crate::TS>::name()) }
             fn decl() -> String { panic!("{} cannot be declared", <Self as $crate::TS>::name()) }
             fn decl_concrete() -> String { panic!("{} cannot be declared", <Self as $crate::TS>::name()) }
         }
+    };
+    ($($($ty:ty),* => $l:literal),*) => { $($(
+        impl_primitives!(@impl $ty => $l, None);
     )*)* };
 }
+// generate impls for numeric primitives, which can be stored in a typed array
+macro_rules! impl_typed_array_primitives {
+    ($($ty:ty => $l:literal as $typed_array:literal),* $(,)?) => { $(
+        impl_primitives!(@impl $ty => $l, Some($typed_array));
+    )* };
+}
 // generate impls for tuples
 macro_rules! impl_tuples {
     ( impl $($i:ident),* ) => {
         impl<$($i: TS),*> TS for ($($i,)*) {
             type WithoutGenerics = (Dummy, );
             fn name() -> String {
-                format!("[{}]", [$(<$i as $crate::TS>::name()),*].join(", "))
+                format!(
+                    "{}[{}]",
+                    render::readonly_modifier(),
+                    [$(<$i as $crate::TS>::name()),*].join(", ")
+                )
             }
             fn inline() -> String {
                 panic!("tuple cannot be inlined!");
@@ -710,6 +994,7 @@ macro_rules! impl_wrapper {
     ($($t:tt)*) => {
         $($t)* {
             type WithoutGenerics = Self;
+            const OBJECT: bool = T::OBJECT;
             fn name() -> String { T::name() }
             fn inline() -> String { T::inline() }
             fn inline_flattened() -> String { T::inline_flattened() }
@@ -739,6 +1024,7 @@ macro_rules! impl_shadow {
     (as $s:ty: $($impl:tt)*) => {
         $($impl)* {
             type WithoutGenerics = <$s as $crate::TS>::WithoutGenerics;
+            const OBJECT: bool = <$s as $crate::TS>::OBJECT;
             fn ident() -> String { <$s as $crate::TS>::ident() }
             fn name() -> String { <$s as $crate::TS>::name() }
             fn inline() -> String { <$s as $crate::TS>::inline() }
@@ -759,6 +1045,7 @@ macro_rules! impl_shadow {
             fn decl() -> String { <$s as $crate::TS>::decl() }
             fn decl_concrete() -> String { <$s as $crate::TS>::decl_concrete() }
             fn output_path() -> Option<&'static std::path::Path> { <$s as $crate::TS>::output_path() }
+            fn crate_dir() -> Option<&'static str> { <$s as $crate::TS>::crate_dir() }
         }
     };
 }
@@ -813,11 +1100,21 @@ impl<T: TS, E: TS> TS for Result<T, E> {
     type WithoutGenerics = Result<Dummy, Dummy>;
 
     fn name() -> String {
-        format!("{{ Ok : {} }} | {{ Err : {} }}", T::name(), E::name())
+        let readonly = render::readonly_modifier();
+        format!(
+            "{{ {readonly}Ok : {} }} | {{ {readonly}Err : {} }}",
+            T::name(),
+            E::name()
+        )
     }
 
     fn inline() -> String {
-        format!("{{ Ok : {} }} | {{ Err : {} }}", T::inline(), E::inline())
+        let readonly = render::readonly_modifier();
+        format!(
+            "{{ {readonly}Ok : {} }} | {{ {readonly}Err : {} }}",
+            T::inline(),
+            E::inline()
+        )
     }
 
     fn schema(export: bool) -> String {
@@ -867,18 +1164,31 @@ impl<T: TS> TS for Vec<T> {
     }
 
     fn name() -> String {
-        format!("Array<{}>", T::name())
+        match render::typed_array::<T>() {
+            Some(typed_array) => typed_array.to_owned(),
+            None => format!("{}<{}>", render::array(), T::name()),
+        }
     }
 
     fn inline() -> String {
-        format!("Array<{}>", T::inline())
+        match render::typed_array::<T>() {
+            Some(typed_array) => typed_array.to_owned(),
+            None => format!("{}<{}>", render::array(), T::inline()),
+        }
     }
 
     fn schema(export: bool) -> String {
-        format!(
-            "{{ \"type\" : \"array\", \"items\" : {} }}",
-            T::schema(export)
-        )
+        match render::typed_array::<T>() {
+            Some(typed_array) => format!(
+                "{{ \"type\" : \"array\", \"items\" : {}, \"typedArray\" : \"{}\" }}",
+                T::schema(export),
+                typed_array
+            ),
+            None => format!(
+                "{{ \"type\" : \"array\", \"items\" : {} }}",
+                T::schema(export)
+            ),
+        }
     }
 
     fn visit_dependencies(v: &mut impl TypeVisitor)
@@ -914,28 +1224,40 @@ const ARRAY_TUPLE_LIMIT: usize = 64;
 impl<T: TS, const N: usize> TS for [T; N] {
     type WithoutGenerics = [Dummy; N];
     fn name() -> String {
-        if N > ARRAY_TUPLE_LIMIT {
+        if N > ARRAY_TUPLE_LIMIT || render::typed_array::<T>().is_some() {
             return Vec::<T>::name();
         }
 
         format!(
-            "[{}]",
+            "{}[{}]",
+            render::readonly_modifier(),
             (0..N).map(|_| T::name()).collect::<Box<[_]>>().join(", ")
         )
     }
 
     fn inline() -> String {
-        if N > ARRAY_TUPLE_LIMIT {
+        if N > ARRAY_TUPLE_LIMIT || render::typed_array::<T>().is_some() {
             return Vec::<T>::inline();
         }
 
         format!(
-            "[{}]",
+            "{}[{}]",
+            render::readonly_modifier(),
             (0..N).map(|_| T::inline()).collect::<Box<[_]>>().join(", ")
         )
     }
 
     fn schema(export: bool) -> String {
+        // unlike a `Vec<T>`, an array is encoded without a length prefix
+        if let Some(typed_array) = render::typed_array::<T>() {
+            return format!(
+                "{{ \"type\" : \"array\", \"items\" : {}, \"length\" : {}, \"typedArray\" : \"{}\" }}",
+                T::schema(export),
+                N,
+                typed_array
+            );
+        }
+
         if N > ARRAY_TUPLE_LIMIT {
             return Vec::<T>::schema(export);
         }
@@ -985,18 +1307,41 @@ impl<K: TS, V: TS, H> TS for HashMap<K, V, H> {
     }
 
     fn name() -> String {
-        format!("{{ [key in {}]?: {} }}", K::name(), V::name())
+        match render::es_map() {
+            Some(map) => format!("{}<{}, {}>", map, K::name(), V::name()),
+            None => format!(
+                "{{ {}[key in {}]?: {} }}",
+                render::readonly_modifier(),
+                K::name(),
+                V::name()
+            ),
+        }
     }
 
     fn inline() -> String {
-        format!("{{ [key in {}]?: {} }}", K::inline(), V::inline())
+        match render::es_map() {
+            Some(map) => format!("{}<{}, {}>", map, K::inline(), V::inline()),
+            None => format!(
+                "{{ {}[key in {}]?: {} }}",
+                render::readonly_modifier(),
+                K::inline(),
+                V::inline()
+            ),
+        }
     }
 
     fn schema(export: bool) -> String {
-        format!(
-            "{{ \"type\" : \"object\", \"additionalProperties\" : {} }}",
-            V::schema(export)
-        )
+        match render::es_map() {
+            Some(_) => format!(
+                "{{ \"type\" : \"object\", \"additionalProperties\" : {}, \"propertyNames\" : {}, \"repr\" : \"Map\" }}",
+                V::schema(export),
+                K::schema(export)
+            ),
+            None => format!(
+                "{{ \"type\" : \"object\", \"additionalProperties\" : {} }}",
+                V::schema(export)
+            ),
+        }
     }
 
     fn visit_dependencies(v: &mut impl TypeVisitor)
@@ -1030,6 +1375,65 @@ impl<K: TS, V: TS, H> TS for HashMap<K, V, H> {
     }
 }
 
+impl<T: TS, H> TS for HashSet<T, H> {
+    type WithoutGenerics = HashSet<Dummy>;
+
+    fn ident() -> String {
+        render::es_set().unwrap_or("Array").to_owned()
+    }
+
+    fn name() -> String {
+        match render::es_set() {
+            Some(set) => format!("{}<{}>", set, T::name()),
+            None => Vec::<T>::name(),
+        }
+    }
+
+    fn inline() -> String {
+        match render::es_set() {
+            Some(set) => format!("{}<{}>", set, T::inline()),
+            None => Vec::<T>::inline(),
+        }
+    }
+
+    fn schema(export: bool) -> String {
+        match render::es_set() {
+            Some(_) => format!(
+                "{{ \"type\" : \"array\", \"items\" : {}, \"repr\" : \"Set\" }}",
+                T::schema(export)
+            ),
+            None => Vec::<T>::schema(export),
+        }
+    }
+
+    fn visit_dependencies(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_dependencies(v);
+    }
+
+    fn visit_generics(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_generics(v);
+        v.visit::<T>();
+    }
+
+    fn decl() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+
+    fn decl_concrete() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+
+    fn inline_flattened() -> String {
+        panic!("{} cannot be flattened", Self::name())
+    }
+}
+
 impl<I: TS> TS for Range<I> {
     type WithoutGenerics = Range<Dummy>;
     fn name() -> String {
@@ -1077,8 +1481,7 @@ impl<I: TS> TS for Range<I> {
 }
 
 impl_shadow!(as Range<I>: impl<I: TS> TS for RangeInclusive<I>);
-impl_shadow!(as Vec<T>: impl<T: TS, H> TS for HashSet<T, H>);
-impl_shadow!(as Vec<T>: impl<T: TS> TS for BTreeSet<T>);
+impl_shadow!(as HashSet<T>: impl<T: TS> TS for BTreeSet<T>);
 impl_shadow!(as HashMap<K, V>: impl<K: TS, V: TS> TS for BTreeMap<K, V>);
 impl_shadow!(as Vec<T>: impl<T: TS> TS for [T]);
 
@@ -1121,7 +1524,7 @@ impl_primitives! { bson::oid::ObjectId => "string" }
 impl_primitives! { bson::Uuid => "string" }
 
 #[cfg(feature = "indexmap-impl")]
-impl_shadow!(as Vec<T>: impl<T: TS> TS for indexmap::IndexSet<T>);
+impl_shadow!(as HashSet<T>: impl<T: TS> TS for indexmap::IndexSet<T>);
 
 #[cfg(feature = "indexmap-impl")]
 impl_shadow!(as HashMap<K, V>: impl<K: TS, V: TS> TS for indexmap::IndexMap<K, V>);
@@ -1140,12 +1543,25 @@ mod bytes {
     impl_shadow!(as Vec<u8>: impl TS for bytes::BytesMut);
 }
 
+impl_typed_array_primitives! {
+    u8 => "number" as "Uint8Array",
+    i8 => "number" as "Int8Array",
+    u16 => "number" as "Uint16Array",
+    i16 => "number" as "Int16Array",
+    u32 => "number" as "Uint32Array",
+    i32 => "number" as "Int32Array",
+    f32 => "number" as "Float32Array",
+    f64 => "number" as "Float64Array",
+    u64 => "bigint" as "BigUint64Array",
+    i64 => "bigint" as "BigInt64Array",
+}
+
 impl_primitives! {
-    u8, i8, NonZeroU8, NonZeroI8,
-    u16, i16, NonZeroU16, NonZeroI16,
-    u32, i32, NonZeroU32, NonZeroI32,
-    usize, isize, NonZeroUsize, NonZeroIsize, f32, f64 => "number",
-    u64, i64, NonZeroU64, NonZeroI64,
+    NonZeroU8, NonZeroI8,
+    NonZeroU16, NonZeroI16,
+    NonZeroU32, NonZeroI32,
+    usize, isize, NonZeroUsize, NonZeroIsize => "number",
+    NonZeroU64, NonZeroI64,
     u128, i128, NonZeroU128, NonZeroI128 => "bigint",
     bool => "boolean",
     char, Path, PathBuf, String, str,
@@ -0,0 +1,130 @@
+//! A registry of all types annotated with `#[ts(export)]`, populated at link time.
+//!
+//! With the `registry` feature enabled, `#[derive(TS)]` registers every exported type, so that
+//! bindings can be generated from a `main` function, an example or a build script instead of
+//! running `cargo test`.
+
+use std::{path::Path, time::Instant};
+
+use crate::{
+    export::{self, Session},
+    Directory, ExportError, ExportReport, ExportSink, TS,
+};
+
+/// A type annotated with `#[ts(export)]`.  
+/// Registrations are submitted by `#[derive(TS)]` and can be enumerated using [`registered`].
+pub struct Registration {
+    rust_path: &'static str,
+    ident: fn() -> String,
+    output_path: fn() -> Option<&'static Path>,
+    export_all: fn(&mut Session, &mut dyn ExportSink) -> Result<(), ExportError>,
+}
+
+inventory::collect!(Registration);
+
+impl Registration {
+    #[doc(hidden)]
+    pub const fn new<T: TS + ?Sized + 'static>(rust_path: &'static str) -> Self {
+        Self {
+            rust_path,
+            ident: T::ident,
+            output_path: T::output_path,
+            export_all: export_all::<T>,
+        }
+    }
+
+    /// The path of the Rust type, e.g `my_crate::api::User`.
+    pub fn rust_path(&self) -> &'static str {
+        self.rust_path
+    }
+
+    /// The identifier of the TypeScript type, without generic parameters.
+    pub fn ident(&self) -> String {
+        (self.ident)()
+    }
+
+    /// The path, relative to the export directory, the type is exported to.
+    pub fn output_path(&self) -> Option<&'static Path> {
+        (self.output_path)()
+    }
+
+    /// Export this type into the given directory, together with all of its dependencies.
+    pub fn export_all_to(&self, out_dir: impl AsRef<Path>) -> Result<(), ExportError> {
+        self.export_all_into(&mut Directory::new(out_dir))
+    }
+
+    /// Export this type into the given sink, together with all of its dependencies.
+    pub fn export_all_into(&self, sink: &mut dyn ExportSink) -> Result<(), ExportError> {
+        export::check::check_if_enabled(|| (self.export_all)(&mut Session::default(), sink))
+    }
+}
+
+fn export_all<T: TS + ?Sized + 'static>(
+    session: &mut Session,
+    sink: &mut dyn ExportSink,
+) -> Result<(), ExportError> {
+    export::export_recursive::<T>(session, sink)
+}
+
+/// Returns all types annotated with `#[ts(export)]`, ordered by their Rust path.
+pub fn registered() -> Vec<&'static Registration> {
+    let mut registrations = inventory::iter::<Registration>
+        .into_iter()
+        .collect::<Vec<_>>();
+    registrations.sort_by_key(|r| r.rust_path);
+    registrations
+}
+
+/// Export all types annotated with `#[ts(export)]`, together with their dependencies, into the
+/// given directory.
+///
+/// This is the equivalent of running the `export_bindings_*` tests generated by `#[ts(export)]`,
+/// but can be called from anywhere, e.g a `main` function or an example:
+/// ```no_run
+/// ts_rs::export_registered("./bindings").expect("could not export bindings");
+/// ```
+/// Types shared between multiple registered types are only exported once.  
+/// If `TS_RS_CHECK` is set, the bindings are checked instead, see [`TS::check_all_in`].
+pub fn export_registered(out_dir: impl AsRef<Path>) -> Result<(), ExportError> {
+    export_registered_into(&mut Directory::new(out_dir))
+}
+
+/// Export all types annotated with `#[ts(export)]`, together with their dependencies, into the
+/// given sink.
+///
+/// Like [`export_registered`], but the bindings can be kept in memory or written into an archive,
+/// e.g to serve them from a development server:
+/// ```no_run
+/// # use std::{collections::BTreeMap, path::PathBuf};
+/// let mut files = BTreeMap::<PathBuf, String>::new();
+/// ts_rs::export_registered_into(&mut files).expect("could not export bindings");
+/// ```
+pub fn export_registered_into(sink: &mut dyn ExportSink) -> Result<(), ExportError> {
+    export_registered_with_report(sink)?;
+    Ok(())
+}
+
+/// Export all types annotated with `#[ts(export)]`, together with their dependencies, into the
+/// given sink, returning a report of what happened.
+///
+/// See [`TS::export_all_with_report`] for what the report contains:
+/// ```no_run
+/// # use ts_rs::Directory;
+/// let report = ts_rs::export_registered_with_report(&mut Directory::new("./bindings"))
+///     .expect("could not export bindings");
+/// std::fs::write("ts-rs-report.json", report.to_json()).unwrap();
+/// ```
+pub fn export_registered_with_report(
+    sink: &mut dyn ExportSink,
+) -> Result<ExportReport, ExportError> {
+    export::check::check_if_enabled(|| {
+        let started = Instant::now();
+        let mut session = Session::default();
+        for registration in registered() {
+            (registration.export_all)(&mut session, sink)?;
+        }
+
+        session.report.duration = started.elapsed();
+        Ok(session.report)
+    })
+}
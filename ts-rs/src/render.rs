@@ -0,0 +1,268 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use crate::{BigIntStyle, Config, TS};
+
+thread_local! {
+    static CURRENT: Cell<RenderOptions> = Cell::new(RenderOptions::default());
+    static ALIASES: RefCell<HashMap<&'static str, String>> = RefCell::new(HashMap::new());
+}
+
+/// Options changing how built-in types, like `Vec<T>`, are represented in TypeScript.
+///
+/// The defaults are the ones of [`Config::default`]. When exporting, the options are set by the
+/// [`Config`] of the crate declaring the exported type. They can be overridden while rendering a
+/// part of a type, e.g. a single field annotated with `#[ts(typed_array)]` or a type annotated
+/// with `#[ts(readonly)]`, using [`RenderOptions::scope`].
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RenderOptions {
+    /// Represent sequences of numeric primitives as typed arrays, e.g `Vec<u8>` as `Uint8Array`
+    pub typed_arrays: bool,
+    /// Mark properties as `readonly` and represent sequences as `ReadonlyArray<T>`
+    pub readonly: bool,
+    /// Represent maps as `Map<K, V>` and sets as `Set<T>` instead of objects and arrays
+    pub es_collections: bool,
+    /// How integers which don't fit into a `number` are represented
+    pub bigint: BigIntStyle,
+    /// Declare structs with named fields as `interface`s instead of type aliases
+    pub interface: bool,
+}
+
+impl RenderOptions {
+    /// Returns the options currently in effect
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+
+    pub fn typed_arrays(mut self, typed_arrays: bool) -> Self {
+        self.typed_arrays = typed_arrays;
+        self
+    }
+
+    pub fn readonly(mut self, readonly: bool) -> Self {
+        self.readonly = readonly;
+        self
+    }
+
+    pub fn es_collections(mut self, es_collections: bool) -> Self {
+        self.es_collections = es_collections;
+        self
+    }
+
+    pub fn bigint(mut self, bigint: BigIntStyle) -> Self {
+        self.bigint = bigint;
+        self
+    }
+
+    pub fn interface(mut self, interface: bool) -> Self {
+        self.interface = interface;
+        self
+    }
+
+    /// Returns these options with the settings of `config`, which is the configuration of the crate
+    /// declaring the type being rendered
+    pub(crate) fn configure(self, config: &Config) -> Self {
+        self.bigint(config.bigint)
+            .typed_arrays(config.typed_arrays)
+            .readonly(config.readonly)
+            .interface(config.interface)
+            .es_collections(config.es_collections)
+    }
+
+    /// Resolves the marks within the schema of a derived type which depend on these options.
+    ///
+    /// A mark `%%typed_arrays:..%%` is replaced by its contents if typed arrays are enabled, and
+    /// removed otherwise. This is the case for the `"typedArray"` of fields which are not annotated
+    /// with `#[ts(typed_array)]`, and likewise for `%%es_collections:..%%`.
+    pub fn resolve_marks(self, schema: &str) -> String {
+        let mut resolved = String::with_capacity(schema.len());
+        let mut rest = schema;
+        while let Some(start) = rest.find(MARK) {
+            resolved.push_str(&rest[..start]);
+            let marked = &rest[start + MARK.len()..];
+            let end = marked.find(MARK).unwrap_or(marked.len());
+            let (setting, contents) = marked[..end].split_once(':').unwrap_or_default();
+            let enabled = match setting {
+                "typed_arrays" => self.typed_arrays,
+                "es_collections" => self.es_collections,
+                _ => false,
+            };
+            if enabled {
+                resolved.push_str(contents);
+            }
+            rest = marked.get(end + MARK.len()..).unwrap_or_default();
+        }
+        resolved.push_str(rest);
+        resolved
+    }
+
+    /// Returns the modifier to put in front of properties and tuples, which is either `readonly `
+    /// or nothing
+    pub fn readonly_modifier(self) -> &'static str {
+        match self.readonly {
+            true => "readonly ",
+            false => "",
+        }
+    }
+
+    /// Calls `f` with these options in effect, restoring the previous options afterwards
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        struct Restore(RenderOptions);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(self)));
+        f()
+    }
+}
+
+/// Delimits the parts of a schema which depend on the [`RenderOptions`], see
+/// [`RenderOptions::resolve_marks`]
+const MARK: &str = "%%";
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            typed_arrays: false,
+            readonly: false,
+            es_collections: false,
+            bigint: BigIntStyle::BigInt,
+            interface: false,
+        }
+    }
+}
+
+/// Returns the name of the primitive type `name`, which is `bigint` for 64 and 128 bit integers
+pub(crate) fn primitive(name: &'static str) -> &'static str {
+    match (name, RenderOptions::current().bigint) {
+        ("bigint", BigIntStyle::Number) => "number",
+        ("bigint", BigIntStyle::String) => "string",
+        _ => name,
+    }
+}
+
+/// Returns the name of the generic array type sequences are represented as
+pub(crate) fn array() -> &'static str {
+    match RenderOptions::current().readonly {
+        true => "ReadonlyArray",
+        false => "Array",
+    }
+}
+
+/// Returns the name of the generic type maps are represented as, if they are represented as an ES
+/// `Map` instead of an object
+pub(crate) fn es_map() -> Option<&'static str> {
+    let options = RenderOptions::current();
+    options.es_collections.then_some(match options.readonly {
+        true => "ReadonlyMap",
+        false => "Map",
+    })
+}
+
+/// Returns the name of the generic type sets are represented as, if they are represented as an ES
+/// `Set` instead of an array
+pub(crate) fn es_set() -> Option<&'static str> {
+    let options = RenderOptions::current();
+    options.es_collections.then_some(match options.readonly {
+        true => "ReadonlySet",
+        false => "Set",
+    })
+}
+
+/// Returns the modifier to put in front of properties and tuples, see
+/// [`RenderOptions::readonly_modifier`]
+pub(crate) fn readonly_modifier() -> &'static str {
+    RenderOptions::current().readonly_modifier()
+}
+
+/// Returns the typed array a sequence of `T` is represented as, if typed arrays are enabled and
+/// `T` can be stored in one
+pub(crate) fn typed_array<T: crate::TS + ?Sized>() -> Option<&'static str> {
+    let options = RenderOptions::current();
+    options
+        .typed_arrays
+        .then_some(T::TYPED_ARRAY)
+        .flatten()
+        // `BigInt64Array` and `BigUint64Array` contain `bigint`s
+        .filter(|array| !array.starts_with("Big") || options.bigint == BigIntStyle::BigInt)
+}
+
+/// Returns the name `T`, which is called `ident`, is referred to by. This is `ident`, unless `T`
+/// was imported under an alias because its name collides with another type.
+#[doc(hidden)]
+pub fn aliased<T: TS + ?Sized>(ident: &str) -> String {
+    ALIASES
+        .with(|aliases| {
+            let aliases = aliases.borrow();
+            match aliases.is_empty() {
+                true => None,
+                false => aliases.get(crate::export::rust_path::<T>()).cloned(),
+            }
+        })
+        .unwrap_or_else(|| ident.to_owned())
+}
+
+/// Intersects the type `inner` with `brand`. `inner` is parenthesized if `&` would otherwise only
+/// apply to a part of it, which is the case for unions, function and conditional types.
+#[doc(hidden)]
+pub fn branded(inner: &str, brand: &str) -> String {
+    match binds_looser_than_intersection(inner) {
+        true => format!("({}) & {}", inner, brand),
+        false => format!("{} & {}", inner, brand),
+    }
+}
+
+/// Returns whether `ty` contains a `|`, `=>` or `?` which is not nested within brackets or a
+/// string literal.
+fn binds_looser_than_intersection(ty: &str) -> bool {
+    let mut depth = 0_usize;
+    let mut chars = ty.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' | '`' => {
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        _ if next == c => break,
+                        _ => (),
+                    }
+                }
+            }
+            '=' if chars.peek() == Some(&'>') => {
+                chars.next();
+                if depth == 0 {
+                    return true;
+                }
+            }
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            '|' | '?' if depth == 0 => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Calls `f` with types referred to by the given aliases, keyed by their rust path, restoring the
+/// previous aliases afterwards
+pub(crate) fn with_aliases<R>(aliases: HashMap<&'static str, String>, f: impl FnOnce() -> R) -> R {
+    struct Restore(HashMap<&'static str, String>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ALIASES.with(|aliases| *aliases.borrow_mut() = std::mem::take(&mut self.0));
+        }
+    }
+
+    let _restore = Restore(ALIASES.with(|current| current.replace(aliases)));
+    f()
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "barrel")]
+#![allow(dead_code)]
+
+use std::fs;
+
+use ts_rs::TS;
+
+#[derive(TS)]
+#[ts(export_to = "barrel/")]
+struct Account {
+    id: AccountId,
+    owner: Owner,
+}
+
+#[derive(TS)]
+#[ts(export_to = "barrel/", brand)]
+struct AccountId(u64);
+
+#[derive(TS)]
+#[ts(export_to = "barrel/people/")]
+struct Owner {
+    name: String,
+}
+
+#[test]
+fn barrel() {
+    let dir = std::env::temp_dir().join("ts-rs-barrel");
+    let _ = fs::remove_dir_all(&dir);
+
+    // a barrel left over from a previous run, re-exporting a type which no longer exists
+    fs::create_dir_all(dir.join("barrel")).unwrap();
+    fs::write(
+        dir.join("barrel/index.ts"),
+        "export type { Removed } from \"./Removed\";\n",
+    )
+    .unwrap();
+
+    Account::export_all_to(&dir).unwrap();
+
+    let manifest = fs::read_to_string(dir.join(".ts-rs-manifest.json")).unwrap();
+    assert!(manifest.contains("\"barrel/index.ts\""));
+    assert!(manifest.contains("\"barrel/people/index.ts\""));
+
+    // re-exports are merged into the existing barrel until it's pruned
+    let exports = |index: &str| {
+        fs::read_to_string(dir.join(index))
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with("export "))
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+    assert!(exports("barrel/index.ts")
+        .contains(&"export type { Removed } from \"./Removed\";".to_owned()));
+    ts_rs::prune(&dir).unwrap();
+
+    let ext = if cfg!(feature = "import-esm") {
+        ".js"
+    } else {
+        ""
+    };
+    assert_eq!(
+        exports("barrel/index.ts"),
+        [
+            format!("export type {{ Account }} from \"./Account{ext}\";"),
+            format!("export {{ AccountId }} from \"./AccountId{ext}\";"),
+            format!("export {{ AccountIdSchema }} from \"./AccountId{ext}\";"),
+            format!("export {{ AccountSchema }} from \"./Account{ext}\";"),
+        ]
+    );
+
+    let index = exports("barrel/people/index.ts");
+    assert!(index.contains(&format!("export type {{ Owner }} from \"./Owner{ext}\";")));
+    assert!(index.contains(&format!("export {{ OwnerSchema }} from \"./Owner{ext}\";")));
+}
@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+use ts_rs::TS;
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct UserId(u64);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/")]
+struct PlainId(u64);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct Nickname(Option<String>);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct Tagged<T>(Vec<T>);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct Nicknames(Vec<Option<String>>);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct Separator(#[ts(type = "\" | \"")] String);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/", brand)]
+struct Callback(#[ts(type = "(value: number) => void")] String);
+
+#[derive(TS)]
+#[ts(export, export_to = "brand/")]
+struct User {
+    id: UserId,
+    nickname: Nickname,
+}
+
+#[test]
+fn branded_newtype() {
+    assert_eq!(
+        UserId::decl(),
+        r#"type UserId = bigint & { readonly __brand: "UserId" };"#
+    );
+    assert_eq!(
+        UserId::constructor().unwrap(),
+        "const UserId = (value: bigint): UserId => value as UserId;"
+    );
+    assert_eq!(PlainId::constructor(), None);
+}
+
+#[test]
+fn branded_union() {
+    assert_eq!(
+        Nickname::decl(),
+        r#"type Nickname = (string | null) & { readonly __brand: "Nickname" };"#
+    );
+}
+
+#[test]
+fn branded_nested_union() {
+    // unions within generics or string literals don't need parentheses
+    assert_eq!(
+        Nicknames::decl(),
+        r#"type Nicknames = Array<string | null> & { readonly __brand: "Nicknames" };"#
+    );
+    assert_eq!(
+        Separator::decl(),
+        r#"type Separator = " | " & { readonly __brand: "Separator" };"#
+    );
+}
+
+#[test]
+fn branded_function() {
+    assert_eq!(
+        Callback::decl(),
+        r#"type Callback = ((value: number) => void) & { readonly __brand: "Callback" };"#
+    );
+}
+
+#[test]
+fn branded_generic() {
+    assert_eq!(
+        Tagged::<String>::decl(),
+        r#"type Tagged<T> = Array<T> & { readonly __brand: "Tagged" };"#
+    );
+    assert_eq!(
+        Tagged::<String>::constructor().unwrap(),
+        "const Tagged = <T>(value: Array<T>): Tagged<T> => value as Tagged<T>;"
+    );
+}
+
+#[test]
+fn branded_schema() {
+    // decoders treat a branded type exactly like the type without a brand
+    assert_eq!(
+        UserId::schema(false),
+        PlainId::schema(false).replace("PlainId", "UserId")
+    );
+    assert_eq!(User::inline(), "{ id: UserId, nickname: Nickname, }");
+}
+
+#[test]
+fn export_constructor() {
+    UserId::export_all().unwrap();
+    let text = std::fs::read_to_string(UserId::default_output_path().unwrap()).unwrap();
+
+    assert!(text.contains("export const UserId = (value: bigint): UserId => value as UserId;"));
+}
@@ -0,0 +1,174 @@
+#![allow(dead_code)]
+
+use std::fs;
+
+use ts_rs::{Bundle, ExportError, TS};
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct Request {
+    user: User,
+    page: Page<User>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct Response {
+    users: Page<User>,
+    #[ts(inline)]
+    cursor: Cursor,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/models/")]
+struct User {
+    id: UserId,
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/models/")]
+struct UserId(u64);
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct Cursor {
+    offset: u32,
+}
+
+mod a {
+    #[derive(ts_rs::TS)]
+    pub struct Error {
+        pub message: String,
+    }
+}
+
+mod b {
+    #[derive(ts_rs::TS)]
+    pub struct Error {
+        pub code: u32,
+    }
+}
+
+#[derive(TS)]
+struct Failure {
+    a: a::Error,
+    b: b::Error,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct Folder {
+    files: Vec<File>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "bundle/")]
+struct File {
+    folder: Option<Box<Folder>>,
+}
+
+fn position(bundle: &str, declaration: &str) -> usize {
+    bundle
+        .find(declaration)
+        .unwrap_or_else(|| panic!("{declaration} is missing from the bundle"))
+}
+
+#[test]
+fn bundle() {
+    let bundle = Bundle::new()
+        .add::<Request>()
+        .add::<Response>()
+        .export_to_string()
+        .unwrap();
+
+    assert!(!bundle.contains("import "));
+
+    // every type is declared once, after its dependencies
+    for declaration in [
+        "export type UserId",
+        "export type User ",
+        "export type Page<T>",
+        "export type Request",
+        "export type Cursor",
+        "export type Response",
+        "export const UserSchema",
+        "export const PageSchema",
+        "export const CursorSchema",
+    ] {
+        assert_eq!(bundle.matches(declaration).count(), 1, "{declaration}");
+    }
+    assert!(position(&bundle, "export type UserId") < position(&bundle, "export type User "));
+    assert!(position(&bundle, "export type User ") < position(&bundle, "export type Page<T>"));
+    assert!(position(&bundle, "export type Page<T>") < position(&bundle, "export type Request"));
+    assert!(position(&bundle, "export type Request") < position(&bundle, "export type Cursor"));
+    assert!(
+        position(&bundle, "export const CursorSchema") < position(&bundle, "export type Response")
+    );
+}
+
+#[test]
+fn write_bundle() {
+    let path = std::env::temp_dir().join("ts-rs-bundle/protocol.ts");
+    let _ = fs::remove_file(&path);
+
+    Bundle::new().add::<Response>().write(&path).unwrap();
+
+    let written = fs::read_to_string(&path).unwrap();
+    if cfg!(feature = "format") {
+        // only written bundles are formatted
+        assert!(written
+            .contains("export type Response = { users: Page<User>; cursor: { offset: number } };"));
+    } else {
+        assert_eq!(
+            written,
+            Bundle::new().add::<Response>().export_to_string().unwrap()
+        );
+    }
+}
+
+#[test]
+fn name_collision() {
+    let error = Bundle::new()
+        .add::<Failure>()
+        .export_to_string()
+        .unwrap_err();
+    let ExportError::NameCollision {
+        name,
+        first,
+        second,
+    } = error
+    else {
+        panic!("expected a name collision, got {error:?}");
+    };
+    assert_eq!(name, "Error");
+    assert_eq!(first, "integration::bundle::a::Error");
+    assert_eq!(second, "integration::bundle::b::Error");
+}
+
+#[test]
+fn cannot_be_exported() {
+    let error = Bundle::new().add::<u32>().export_to_string().unwrap_err();
+    assert!(matches!(error, ExportError::CannotBeExported(_)));
+}
+
+#[test]
+fn cyclic_schemas() {
+    let bundle = Bundle::new().add::<Folder>().export_to_string().unwrap();
+
+    // `FolderSchema` is declared after `FileSchema`, so it is only read once it is accessed
+    assert!(
+        position(&bundle, "export const FileSchema")
+            < position(&bundle, "export const FolderSchema")
+    );
+    assert!(bundle.contains(r#"get "Folder"() { return FolderSchema; },"#));
+    assert!(bundle
+        .contains(r#"get "Box<Folder>"() { return { ...FolderSchema, "generics": {  } }; },"#));
+    assert!(bundle.contains(r#""File": FileSchema,"#));
+}
@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+
+use std::{fs, path::PathBuf};
+
+use ts_rs::{ExportError, TS};
+
+#[derive(TS)]
+#[ts(export_to = "check/")]
+struct Order {
+    id: u32,
+    customer: Customer,
+}
+
+#[derive(TS)]
+#[ts(export_to = "check/")]
+struct Customer {
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "check/merged.ts")]
+struct Invoice {
+    order: Order,
+    total: f64,
+}
+
+#[derive(TS)]
+#[ts(export_to = "check/merged.ts")]
+struct Receipt {
+    invoice: Invoice,
+}
+
+fn out_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("ts-rs-check").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn up_to_date() {
+    let dir = out_dir("up_to_date");
+    Order::export_all_to(&dir).unwrap();
+
+    Order::check_all_in(&dir).unwrap();
+}
+
+#[test]
+fn stale_file() {
+    let dir = out_dir("stale_file");
+    Order::export_all_to(&dir).unwrap();
+
+    let path = dir.join("check/Customer.ts");
+    let stale = fs::read_to_string(&path)
+        .unwrap()
+        .replace("name: string", "name: number");
+    fs::write(&path, &stale).unwrap();
+
+    let Err(ExportError::OutOfDate(diff)) = Order::check_all_in(&dir) else {
+        panic!("expected the bindings to be out of date")
+    };
+    assert!(diff.contains("Customer.ts (on disk)"));
+    assert!(diff.contains("-export type Customer = { name: number"));
+    assert!(diff.contains("+export type Customer = { name: string"));
+    assert!(!diff.contains("Order.ts"));
+
+    // nothing was written
+    assert_eq!(fs::read_to_string(&path).unwrap(), stale);
+}
+
+#[test]
+fn missing_file() {
+    let dir = out_dir("missing_file");
+    Order::export_all_to(&dir).unwrap();
+    fs::remove_file(dir.join("check/Order.ts")).unwrap();
+
+    let Err(ExportError::OutOfDate(diff)) = Order::check_all_in(&dir) else {
+        panic!("expected the bindings to be out of date")
+    };
+    assert!(diff.contains("@@ -0,0 +1,"));
+    assert!(!dir.join("check/Order.ts").exists());
+}
+
+#[test]
+fn merged_file() {
+    let dir = out_dir("merged_file");
+    Receipt::export_all_to(&dir).unwrap();
+
+    // each type of a merged file can be checked on its own
+    Receipt::check_all_in(&dir).unwrap();
+    Invoice::check_all_in(&dir).unwrap();
+
+    let path = dir.join("check/merged.ts");
+    let stale = fs::read_to_string(&path)
+        .unwrap()
+        .replace("total: number", "total: string");
+    fs::write(&path, stale).unwrap();
+
+    let Err(ExportError::OutOfDate(diff)) = Receipt::check_all_in(&dir) else {
+        panic!("expected the bindings to be out of date")
+    };
+    assert_eq!(diff.matches("merged.ts (on disk)").count(), 1);
+    let invoice = if cfg!(feature = "format") {
+        "+export type Invoice = { order: Order; total: number };"
+    } else {
+        "+export type Invoice = { order: Order, total: number, };"
+    };
+    assert!(diff.contains(invoice));
+}
+
+#[test]
+fn stale_declaration() {
+    let dir = out_dir("stale_declaration");
+    Receipt::export_all_to(&dir).unwrap();
+
+    // the declaration of a type which was removed since
+    let path = dir.join("check/merged.ts");
+    let stale = format!(
+        "{}\nexport type Refund = {{ receipt: Receipt, }};\n",
+        fs::read_to_string(&path).unwrap()
+    );
+    fs::write(&path, &stale).unwrap();
+
+    let Err(ExportError::OutOfDate(diff)) = Receipt::check_all_in(&dir) else {
+        panic!("expected the stale declaration to be reported")
+    };
+    assert!(diff.contains("-export type Refund = { receipt: Receipt, };"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), stale);
+}
+
+#[test]
+#[cfg(feature = "barrel")]
+fn barrel() {
+    let dir = out_dir("barrel");
+    Order::export_all_to(&dir).unwrap();
+    Order::check_all_in(&dir).unwrap();
+
+    let index = dir.join("check/index.ts");
+    let stale = fs::read_to_string(&index)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.contains("{ Customer }"))
+        .map(|line| format!("{line}\n"))
+        .collect::<String>();
+    fs::write(&index, &stale).unwrap();
+
+    let Err(ExportError::OutOfDate(diff)) = Order::check_all_in(&dir) else {
+        panic!("expected the barrel to be out of date")
+    };
+    assert!(diff.contains("index.ts (on disk)"));
+    assert!(diff.contains("+export type { Customer } from \"./Customer"));
+    assert_eq!(fs::read_to_string(&index).unwrap(), stale);
+
+    fs::remove_file(&index).unwrap();
+    assert!(Order::check_all_in(&dir).is_err());
+    assert!(!index.exists());
+}
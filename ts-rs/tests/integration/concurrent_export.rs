@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+
+use std::{fs, path::PathBuf};
+
+use ts_rs::TS;
+
+#[derive(TS)]
+#[ts(export_to = "shared.ts")]
+struct Alpha {
+    a: u32,
+}
+
+#[derive(TS)]
+#[ts(export_to = "shared.ts")]
+struct Beta {
+    b: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "shared.ts")]
+struct Gamma {
+    c: bool,
+}
+
+#[derive(TS)]
+#[ts(export_to = "shared.ts")]
+struct Delta {
+    d: Vec<u8>,
+}
+
+const NOTE: &str = "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n";
+
+fn out_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join("ts-rs-concurrent-export")
+        .join(test);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn concurrent_threads() {
+    let dir = out_dir("threads");
+
+    let handles = vec![
+        std::thread::spawn({
+            let dir = dir.clone();
+            move || Alpha::export_all_to(dir)
+        }),
+        std::thread::spawn({
+            let dir = dir.clone();
+            move || Beta::export_all_to(dir)
+        }),
+        std::thread::spawn({
+            let dir = dir.clone();
+            move || Gamma::export_all_to(dir)
+        }),
+    ];
+    for handle in handles {
+        handle.join().unwrap().unwrap();
+    }
+
+    let contents = fs::read_to_string(dir.join("shared.ts")).unwrap();
+    for decl in ["export type Alpha", "export type Beta", "export type Gamma"] {
+        assert_eq!(contents.matches(decl).count(), 1, "{contents}");
+    }
+}
+
+#[test]
+fn merge_with_other_process() {
+    let dir = out_dir("process");
+
+    // `Alpha` was exported to the same file by another test binary during this run
+    let other = format!("{NOTE}\nexport type Alpha = {{ a: number, }};\n");
+    fs::write(dir.join("shared.ts"), other).unwrap();
+    fs::write(
+        dir.join(".ts-rs-manifest.json"),
+        "{\n  \"files\": {\n    \"shared.ts\": []\n  }\n}\n",
+    )
+    .unwrap();
+
+    Delta::export_all_to(&dir).unwrap();
+
+    let contents = fs::read_to_string(dir.join("shared.ts")).unwrap();
+    assert!(contents.contains("export type Alpha = { a: number, };"));
+    let delta = if cfg!(feature = "format") {
+        "export type Delta = { d: Array<number> };"
+    } else {
+        "export type Delta = { d: Array<number>, };"
+    };
+    assert!(contents.contains(delta));
+}
+
+#[test]
+fn shorter_rewrite() {
+    let dir = out_dir("shorter");
+
+    // a previous version of `Alpha` with a much longer declaration
+    let long = format!(
+        "{NOTE}\nexport type Alpha = {{ a: number, {} }};\n",
+        "padding: string, ".repeat(100)
+    );
+    fs::write(dir.join("shared.ts"), long).unwrap();
+    fs::write(
+        dir.join(".ts-rs-manifest.json"),
+        "{\n  \"files\": {\n    \"shared.ts\": []\n  }\n}\n",
+    )
+    .unwrap();
+
+    Alpha::export_all_to(&dir).unwrap();
+
+    let contents = fs::read_to_string(dir.join("shared.ts")).unwrap();
+    assert!(!contents.contains("padding"));
+    let end = if cfg!(feature = "format") {
+        "};\n"
+    } else {
+        "}\n"
+    };
+    assert!(contents.ends_with(end));
+    assert!(!dir
+        .read_dir()
+        .unwrap()
+        .any(|e| e.unwrap().file_name().to_string_lossy().ends_with(".tmp")));
+}
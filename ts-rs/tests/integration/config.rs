@@ -0,0 +1,470 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ts_rs::{
+    BigIntStyle, Config, ExportError, FileNameCase, Format, Formatter, ImportExtension, Output,
+    Quotes, RenderOptions, TypeVisitor, TS,
+};
+
+#[derive(TS)]
+#[ts(export_to = "config/")]
+struct Transfer {
+    amount: u64,
+    id: u128,
+    small: u32,
+    #[ts(typed_array)]
+    history: Vec<i64>,
+}
+
+/// Directory of the crate declaring `Foreign`
+const FOREIGN: &str = concat!(env!("CARGO_TARGET_TMPDIR"), "/ts-rs-config-foreign");
+
+/// A type declared by another crate, which is configured in `FOREIGN`
+struct Foreign;
+
+impl TS for Foreign {
+    type WithoutGenerics = Self;
+
+    fn decl() -> String {
+        format!(
+            "type Foreign = {{ id: {}, data: {} }};",
+            u64::name(),
+            Vec::<u8>::name()
+        )
+    }
+    fn decl_concrete() -> String {
+        Self::decl()
+    }
+    fn name() -> String {
+        "Foreign".to_owned()
+    }
+    fn inline() -> String {
+        Self::name()
+    }
+    fn inline_flattened() -> String {
+        panic!("Foreign cannot be flattened")
+    }
+    fn schema(_: bool) -> String {
+        String::new()
+    }
+    fn output_path() -> Option<&'static Path> {
+        Some(ts_rs::type_output_path(
+            Self::crate_dir(),
+            "",
+            "ForeignType",
+        ))
+    }
+    fn crate_dir() -> Option<&'static str> {
+        Some(FOREIGN)
+    }
+}
+
+/// Directory of the crate declaring `Role` and `Account`, which exports JavaScript
+const JAVASCRIPT: &str = concat!(env!("CARGO_TARGET_TMPDIR"), "/ts-rs-config-javascript");
+
+struct Role;
+
+impl TS for Role {
+    type WithoutGenerics = Self;
+
+    fn decl() -> String {
+        "type Role = \"Admin\" | \"User\";".to_owned()
+    }
+    fn decl_concrete() -> String {
+        Self::decl()
+    }
+    fn name() -> String {
+        "Role".to_owned()
+    }
+    fn inline() -> String {
+        Self::name()
+    }
+    fn inline_flattened() -> String {
+        panic!("Role cannot be flattened")
+    }
+    fn schema(export: bool) -> String {
+        let schema = "{ \"type\": \"enum\", \"name\": \"Role\" }";
+        match export {
+            true => format!("const RoleSchema = {};", schema),
+            false => schema.to_owned(),
+        }
+    }
+    fn output_path() -> Option<&'static Path> {
+        Some(ts_rs::type_output_path(Self::crate_dir(), "", "Role"))
+    }
+    fn crate_dir() -> Option<&'static str> {
+        Some(JAVASCRIPT)
+    }
+}
+
+struct Account;
+
+impl TS for Account {
+    type WithoutGenerics = Self;
+
+    fn decl() -> String {
+        "type Account = { role: Role };".to_owned()
+    }
+    fn decl_concrete() -> String {
+        Self::decl()
+    }
+    fn name() -> String {
+        "Account".to_owned()
+    }
+    fn inline() -> String {
+        Self::name()
+    }
+    fn inline_flattened() -> String {
+        panic!("Account cannot be flattened")
+    }
+    fn schema(export: bool) -> String {
+        let schema = "{ \"type\": \"struct\", \"definitions\": { \"Role\": RoleSchema } }";
+        match export {
+            true => format!("const AccountSchema = {};", schema),
+            false => schema.to_owned(),
+        }
+    }
+    fn visit_schema_dependencies(v: &mut impl TypeVisitor) {
+        v.visit::<Role>();
+    }
+    fn visit_dependencies(v: &mut impl TypeVisitor) {
+        v.visit::<Role>();
+    }
+    fn output_path() -> Option<&'static Path> {
+        Some(ts_rs::type_output_path(Self::crate_dir(), "", "Account"))
+    }
+    fn crate_dir() -> Option<&'static str> {
+        Some(JAVASCRIPT)
+    }
+}
+
+fn workspace(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("ts-rs-config").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("crate")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crate\"]\n\n\
+         [workspace.metadata.ts-rs]\nout_dir = \"bindings\"\nbigint = \"string\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn defaults() {
+    let dir = std::env::temp_dir().join("ts-rs-config").join("empty");
+    fs::create_dir_all(&dir).unwrap();
+
+    let config = Config::load(&dir).unwrap();
+    assert_eq!(config.out_dir, None);
+    assert_eq!(config.bigint, BigIntStyle::BigInt);
+    assert_eq!(config.output, Output::TypeScript);
+    assert_eq!(config.format.is_some(), cfg!(feature = "format"));
+    assert!(!config.typed_arrays);
+    assert!(!config.readonly);
+    assert!(!config.interface);
+    assert!(!config.es_collections);
+}
+
+#[test]
+fn per_crate_overrides() {
+    let dir = workspace("overrides");
+    fs::write(
+        dir.join("crate/Cargo.toml"),
+        "[package]\nname = \"crate\"\n\n[package.metadata.ts-rs]\nimport_extension = \"js\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("crate/ts-rs.toml"),
+        "import_extension = \"ts\"\noutput = \"js\"\nformat = false\ntyped_arrays = true\n\
+         readonly = true\ninterface = true\nes_collections = true\n",
+    )
+    .unwrap();
+
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.out_dir, Some(dir.join("bindings")));
+    assert_eq!(config.import_extension, ImportExtension::Ts);
+    assert_eq!(config.output, Output::JavaScript);
+    assert_eq!(config.bigint, BigIntStyle::String);
+    assert_eq!(config.format, None);
+    assert!(config.typed_arrays);
+    assert!(config.readonly);
+    assert!(config.interface);
+    assert!(config.es_collections);
+
+    // the workspace itself is not affected by the configuration of its members
+    let config = Config::load(&dir).unwrap();
+    assert_eq!(
+        config.import_extension,
+        Config::load("/").unwrap().import_extension
+    );
+}
+
+#[test]
+fn invalid() {
+    let dir = workspace("invalid");
+    fs::write(dir.join("crate/ts-rs.toml"), "bigint = \"float\"\n").unwrap();
+
+    let Err(ExportError::Config { path, message }) = Config::load(dir.join("crate")) else {
+        panic!("expected the configuration to be invalid")
+    };
+    assert_eq!(path, dir.join("crate/ts-rs.toml"));
+    assert_eq!(message, "invalid value for `bigint`: \"float\"");
+
+    fs::write(dir.join("crate/ts-rs.toml"), "outdir = \"bindings\"\n").unwrap();
+    let error = Config::load(dir.join("crate")).unwrap_err();
+    assert!(error.to_string().ends_with("unknown setting `outdir`"));
+}
+
+#[test]
+fn bigint() {
+    let options = RenderOptions::current().typed_arrays(true);
+
+    let decl = options.scope(Transfer::decl);
+    assert_eq!(
+        decl,
+        "type Transfer = { amount: bigint, id: bigint, small: number, history: BigInt64Array, };"
+    );
+
+    let decl = options.bigint(BigIntStyle::Number).scope(Transfer::decl);
+    assert_eq!(
+        decl,
+        "type Transfer = { amount: number, id: number, small: number, history: Array<number>, };"
+    );
+
+    let decl = options.bigint(BigIntStyle::String).scope(Transfer::decl);
+    assert_eq!(
+        decl,
+        "type Transfer = { amount: string, id: string, small: number, history: Array<string>, };"
+    );
+}
+
+#[test]
+fn base() {
+    let dir = workspace("base");
+    fs::write(
+        dir.join("crate/Cargo.toml"),
+        "[package]\nname = \"crate\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crate\"]\n",
+    )
+    .unwrap();
+
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.base, None);
+
+    fs::write(dir.join("ts-rs.toml"), "base = \"workspace\"\n").unwrap();
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.base, Some(dir.clone()));
+    if std::env::var_os("TS_RS_EXPORT_DIR").is_none() {
+        assert_eq!(config.export_dir(), dir.join("./bindings"));
+    }
+
+    fs::write(dir.join("crate/ts-rs.toml"), "base = \"crate\"\n").unwrap();
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.base, Some(dir.join("crate")));
+
+    fs::write(dir.join("crate/ts-rs.toml"), "base = \"../frontend\"\n").unwrap();
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.base, Some(dir.join("crate/../frontend")));
+
+    // an explicit `out_dir` is not affected by the base
+    fs::write(dir.join("crate/ts-rs.toml"), "out_dir = \"generated\"\n").unwrap();
+    let config = Config::load(dir.join("crate")).unwrap();
+    if std::env::var_os("TS_RS_EXPORT_DIR").is_none() {
+        assert_eq!(config.export_dir(), dir.join("crate/generated"));
+    }
+}
+
+#[test]
+fn header() {
+    let dir = workspace("header");
+    fs::write(
+        dir.join("crate/ts-rs.toml"),
+        "header = \"\"\"\n/* eslint-disable */\n// ts-rs {version}, hash {hash}\n\"\"\"\n",
+    )
+    .unwrap();
+
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(
+        config.header,
+        "/* eslint-disable */\n// ts-rs {version}, hash {hash}"
+    );
+
+    let body = "\nexport type A = number;\n";
+    assert!(config.is_generated(&format!(
+        "/* eslint-disable */\n// ts-rs 10.0.0, hash 0123456789abcdef\n{body}"
+    )));
+    assert!(!config.is_generated(&format!("/* eslint-disable */\n{body}")));
+    assert!(!config.is_generated(body));
+
+    // files exported before the header was configured
+    let default = Config::default().header;
+    assert!(config.is_generated(&format!("{default}\n{body}")));
+}
+
+#[test]
+fn format() {
+    let dir = workspace("format");
+    fs::write(
+        dir.join("ts-rs.toml"),
+        "[format]\nformatter = \"builtin\"\nindent_width = 4\nquotes = \"single\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("crate/ts-rs.toml"),
+        "[format]\nsemicolons = false\nline_width = 100\n",
+    )
+    .unwrap();
+
+    let format = Config::load(dir.join("crate")).unwrap().format.unwrap();
+    assert_eq!(format.formatter, Formatter::BuiltIn);
+    assert_eq!(format.indent_width, 4);
+    assert_eq!(format.line_width, 100);
+    assert_eq!(format.quotes, Quotes::Single);
+    assert!(!format.semicolons);
+
+    // a crate can opt out of the formatting configured for its workspace
+    fs::write(dir.join("crate/ts-rs.toml"), "format = false\n").unwrap();
+    assert_eq!(Config::load(dir.join("crate")).unwrap().format, None);
+
+    fs::write(
+        dir.join("crate/ts-rs.toml"),
+        "[format]\ndprint_config = \"dprint.json\"\n",
+    )
+    .unwrap();
+    let error = Config::load(dir.join("crate")).unwrap_err();
+    assert!(error.to_string().ends_with("requires dprint as formatter"));
+
+    if cfg!(feature = "format") {
+        fs::write(
+            dir.join("crate/ts-rs.toml"),
+            "[format]\nformatter = \"dprint\"\ndprint_config = \"dprint.json\"\n",
+        )
+        .unwrap();
+        let format = Config::load(dir.join("crate")).unwrap().format.unwrap();
+        assert_eq!(format.dprint_config, Some(dir.join("crate/dprint.json")));
+    }
+    assert_eq!(Format::default().indent_width, 2);
+}
+
+#[test]
+fn file_names() {
+    let dir = workspace("file_names");
+    fs::write(dir.join("ts-rs.toml"), "file_names = \"kebab-case\"\n").unwrap();
+    let config = Config::load(dir.join("crate")).unwrap();
+    assert_eq!(config.file_names, FileNameCase::Kebab);
+
+    fs::write(dir.join("ts-rs.toml"), "file_names = \"kebab\"\n").unwrap();
+    assert!(Config::load(dir.join("crate")).is_err());
+
+    let cases = [
+        (
+            "UserProfile",
+            ["UserProfile", "userProfile", "user-profile", "user_profile"],
+        ),
+        (
+            "HTTPServer2",
+            ["HttpServer2", "httpServer2", "http-server2", "http_server2"],
+        ),
+        (
+            "ts_rs_test_type_a",
+            [
+                "TsRsTestTypeA",
+                "tsRsTestTypeA",
+                "ts-rs-test-type-a",
+                "ts_rs_test_type_a",
+            ],
+        ),
+    ];
+    for (name, expected) in cases {
+        let converted = [
+            FileNameCase::Pascal,
+            FileNameCase::Camel,
+            FileNameCase::Kebab,
+            FileNameCase::Snake,
+        ]
+        .map(|case| case.apply(name));
+        assert_eq!(converted, expected);
+        assert_eq!(FileNameCase::None.apply(name), name);
+    }
+
+    // without a configuration, files are named after their type
+    assert_eq!(
+        Transfer::output_path().unwrap(),
+        PathBuf::from("config/Transfer.ts")
+    );
+}
+
+#[test]
+fn declaring_crate() {
+    fs::create_dir_all(FOREIGN).unwrap();
+    fs::write(
+        Path::new(FOREIGN).join("ts-rs.toml"),
+        "file_names = \"kebab-case\"\nbigint = \"string\"\ntyped_arrays = true\n\
+         header = \"// foreign\"\nimport_extension = \"js\"\n",
+    )
+    .unwrap();
+
+    // the bindings follow the configuration of the crate declaring the type, not this one
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    Foreign::export_all_into(&mut files).unwrap();
+    let file = &files[Path::new("foreign-type.ts")];
+    assert!(file.starts_with("// foreign\n"));
+    let foreign = if cfg!(feature = "format") {
+        "export type Foreign = { id: string; data: Uint8Array };"
+    } else {
+        "export type Foreign = { id: string, data: Uint8Array };"
+    };
+    assert!(file.contains(foreign));
+
+    // so does its barrel
+    if cfg!(feature = "barrel") {
+        let index = &files[Path::new("index.ts")];
+        assert!(index.starts_with("// foreign\n"));
+        assert!(index.contains("export type { Foreign } from \"./foreign-type.js\";"));
+    }
+
+    // outside of an export, the defaults apply
+    assert_eq!(
+        Foreign::decl(),
+        "type Foreign = { id: bigint, data: Array<number> };"
+    );
+}
+
+#[test]
+fn javascript() {
+    fs::create_dir_all(JAVASCRIPT).unwrap();
+    fs::write(
+        Path::new(JAVASCRIPT).join("ts-rs.toml"),
+        "output = \"js\"\n",
+    )
+    .unwrap();
+
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    Account::export_all_into(&mut files).unwrap();
+    assert!(files.contains_key(Path::new("Role.d.ts")));
+    assert!(files.contains_key(Path::new("Role.js")));
+    assert!(!files.contains_key(Path::new("Account.ts")));
+
+    // the types, and the schema as value declared elsewhere
+    let declarations = &files[Path::new("Account.d.ts")];
+    assert!(declarations.contains("export type Account = { role: Role };"));
+    assert!(declarations.contains("export declare const AccountSchema: Record<string, unknown>;"));
+
+    // only the schema, importing the ones it references from their `.js` file
+    let values = &files[Path::new("Account.js")];
+    assert!(values.contains("import { RoleSchema } from \"./Role.js\";"));
+    assert!(values.contains("export const AccountSchema = {"));
+    assert!(!values.contains("import type"));
+    assert!(!values.contains("export type"));
+}
@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use ts_rs::{RenderOptions, TS};
+
+#[derive(TS, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[ts(export, export_to = "es_collections/")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "es_collections/")]
+struct Graph {
+    #[ts(es_collections)]
+    edges: HashMap<Point, Vec<Point>>,
+    #[ts(es_collections)]
+    roots: Option<BTreeSet<Point>>,
+    #[ts(es_collections)]
+    weights: Vec<BTreeMap<u64, f32>>,
+    labels: HashMap<String, String>,
+    tags: HashSet<String>,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "es_collections/", readonly)]
+struct Frozen {
+    #[ts(es_collections)]
+    points: HashMap<Point, String>,
+    #[ts(es_collections)]
+    visited: HashSet<Point>,
+}
+
+#[test]
+fn es_collection_fields() {
+    assert_eq!(
+        Graph::decl(),
+        "type Graph = { \
+            edges: Map<Point, Array<Point>>, \
+            roots: Set<Point> | null, \
+            weights: Array<Map<bigint, number>>, \
+            labels: { [key in string]?: string }, \
+            tags: Array<string>, \
+        };"
+    );
+
+    // set by `es_collections = true` in the configuration of the crate declaring the type
+    let options = RenderOptions::current().es_collections(true);
+    let decl = options.scope(Graph::decl);
+    assert!(decl.contains("labels: Map<string, string>, tags: Set<string>, "));
+}
+
+#[test]
+fn readonly_es_collections() {
+    assert_eq!(
+        Frozen::decl(),
+        "type Frozen = { \
+            readonly points: ReadonlyMap<Point, string>, \
+            readonly visited: ReadonlySet<Point>, \
+        };"
+    );
+}
+
+#[test]
+fn es_collections_schema() {
+    let schema = Graph::schema(false);
+    assert_eq!(schema.matches(r#""esCollections": true"#).count(), 3);
+
+    let options = RenderOptions::current().es_collections(true);
+    let schema = options.scope(|| Graph::schema(false));
+    assert_eq!(schema.matches(r#""esCollections": true"#).count(), 5);
+
+    let map = options.scope(|| HashMap::<Point, u8>::schema(false));
+    assert!(map.contains(r#""repr" : "Map""#));
+    let set = options.scope(|| HashSet::<u8>::schema(false));
+    assert!(set.contains(r#""repr" : "Set""#));
+}
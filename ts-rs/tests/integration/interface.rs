@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+
+use ts_rs::{RenderOptions, TS};
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/", interface)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/")]
+struct Metadata {
+    created: String,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/", interface)]
+struct Marker {
+    label: String,
+    #[ts(flatten)]
+    position: Point<f32>,
+    #[ts(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/")]
+enum Shape {
+    Circle { radius: f32 },
+    Square { side: f32 },
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/", interface)]
+struct Drawing {
+    name: String,
+    #[ts(flatten)]
+    shape: Shape,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/", interface = false)]
+struct Alias {
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "interface/")]
+struct Labeled {
+    label: String,
+    #[ts(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(TS)]
+#[ts(export_to = "interface/merged.ts", interface)]
+struct Second {
+    first: First,
+}
+
+#[derive(TS)]
+#[ts(export_to = "interface/merged.ts", interface)]
+struct First {
+    value: i32,
+}
+
+#[test]
+fn interface() {
+    assert_eq!(Point::<()>::decl(), "interface Point<T> { x: T, y: T, }");
+    assert_eq!(
+        Point::<f32>::decl_concrete(),
+        "interface Point { x: number, y: number, }"
+    );
+    assert_eq!(Alias::decl(), "type Alias = { name: string, };");
+}
+
+#[test]
+fn interface_option() {
+    assert_eq!(
+        Labeled::decl(),
+        "type Labeled = { label: string, created: string, };"
+    );
+    assert!(Labeled::dependencies().is_empty());
+
+    // set by `interface = true` in the configuration of the crate declaring the type
+    let options = RenderOptions::current().interface(true);
+    assert_eq!(
+        options.scope(Labeled::decl),
+        "interface Labeled extends Metadata { label: string, }"
+    );
+    let deps = options.scope(Labeled::dependencies);
+    assert!(deps.iter().any(|dep| dep.ts_name == "Metadata"));
+    assert_eq!(
+        options.scope(Alias::decl),
+        "type Alias = { name: string, };"
+    );
+}
+
+#[test]
+fn flattened_fields_are_extended() {
+    assert_eq!(
+        Marker::decl(),
+        "interface Marker extends Point<number>, Metadata { label: string, }"
+    );
+    assert!(Marker::OBJECT);
+
+    let deps = Marker::dependencies();
+    assert!(deps.iter().any(|dep| dep.ts_name == "Point"));
+    assert!(deps.iter().any(|dep| dep.ts_name == "Metadata"));
+}
+
+#[test]
+fn union_is_not_extended() {
+    // an interface cannot extend a union, so we fall back to a type alias
+    assert_eq!(
+        Drawing::decl(),
+        "type Drawing = { name: string, } & ({ \"Circle\": { radius: number, } } | { \"Square\": { side: number, } });"
+    );
+    assert!(!Drawing::OBJECT);
+    assert!(Drawing::dependencies().is_empty());
+}
+
+#[test]
+fn merge_interfaces() {
+    Second::export_all().unwrap();
+    let text = std::fs::read_to_string(Second::default_output_path().unwrap()).unwrap();
+
+    let first = text.find("export interface First ").unwrap();
+    let second = text.find("export interface Second ").unwrap();
+    assert!(first < second);
+    assert_eq!(text.matches("export interface First ").count(), 1);
+    assert_eq!(text.matches("export interface Second ").count(), 1);
+}
@@ -1,16 +1,23 @@
 #![allow(dead_code, unused)]
 
 mod arrays;
+mod barrel;
 mod bound;
+mod brand;
 mod bson;
+mod bundle;
+mod check;
 mod chrono;
 mod complex_flattened_type;
 mod concrete_generic;
+mod concurrent_export;
+mod config;
 mod docs;
 mod enum_flattening;
 mod enum_flattening_nested;
 mod enum_struct_rename_all;
 mod enum_variant_annotation;
+mod es_collections;
 mod export_manually;
 mod field_rename;
 mod flatten;
@@ -24,6 +31,7 @@ mod impl_primitive;
 mod imports;
 mod indexmap;
 mod infer_as;
+mod interface;
 mod issue_168;
 mod issue_232;
 mod issue_308;
@@ -34,13 +42,20 @@ mod issue_80;
 mod leading_colon;
 mod lifetimes;
 mod list;
+mod merge;
+mod module_paths;
+mod name_collision;
 mod nested;
 mod optional_field;
 mod path_bug;
+mod prune;
 mod ranges;
 mod raw_idents;
+mod readonly;
 mod recursion_limit;
 mod references;
+mod registry;
+mod report;
 mod same_file_export;
 mod self_referential;
 mod semver;
@@ -48,6 +63,7 @@ mod serde_json;
 mod serde_skip_with_default;
 mod serde_with;
 mod simple;
+mod sink;
 mod skip;
 mod slices;
 mod struct_rename;
@@ -58,6 +74,7 @@ mod top_level_type_override;
 mod tuple;
 mod type_as;
 mod type_override;
+mod typed_arrays;
 mod union;
 mod union_named_serde_skip;
 mod union_rename;
@@ -0,0 +1,93 @@
+#![allow(dead_code)]
+
+use std::{fs, path::PathBuf};
+
+use ts_rs::TS;
+
+/// A branded identifier
+#[derive(TS)]
+#[ts(export_to = "merge.ts", brand)]
+struct Id(u64);
+
+#[derive(TS)]
+#[ts(export_to = "merge.ts")]
+struct Node {
+    id: Id,
+    #[ts(type = "{\n\n  value: string }")]
+    payload: String,
+    children: Vec<Leaf>,
+}
+
+/// A leaf of the tree
+#[derive(TS)]
+#[ts(export_to = "merge.ts")]
+struct Leaf {
+    weight: f32,
+}
+
+fn out_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("ts-rs-merge").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn independent_of_export_order() {
+    let forward = out_dir("forward");
+    Id::export_all_to(&forward).unwrap();
+    Leaf::export_all_to(&forward).unwrap();
+    Node::export_all_to(&forward).unwrap();
+
+    let backward = out_dir("backward");
+    Node::export_all_to(&backward).unwrap();
+    Leaf::export_all_to(&backward).unwrap();
+    Id::export_all_to(&backward).unwrap();
+
+    let forward = fs::read_to_string(forward.join("merge.ts")).unwrap();
+    let backward = fs::read_to_string(backward.join("merge.ts")).unwrap();
+    assert_eq!(forward, backward);
+
+    // every declaration appears exactly once, followed by its schema
+    for name in ["Id", "Leaf", "Node"] {
+        assert_eq!(forward.matches(&format!("export type {name} ")).count(), 1);
+        assert_eq!(forward.matches(&format!("export const {name}Schema ")).count(), 1);
+    }
+    let position = |text: &str| forward.find(text).unwrap();
+    assert!(position("export type Id ") < position("export const IdSchema "));
+    assert!(position("export const IdSchema ") < position("export type Leaf "));
+    assert!(position("export type Leaf ") < position("export const LeafSchema "));
+    assert!(position("export const LeafSchema ") < position("export type Node "));
+
+    // docs, constructors and empty lines within declarations are kept
+    assert!(forward.contains("/**\n * A leaf of the tree\n */\nexport type Leaf "));
+    assert!(forward.contains("export const Id = (value: bigint): Id => value as Id;"));
+    // unless the formatter removes them
+    if !cfg!(feature = "format") {
+        assert!(forward.contains("payload: {\n\n  value: string }"));
+    }
+}
+
+#[test]
+fn replace_declaration() {
+    let reference = out_dir("reference");
+    Node::export_all_to(&reference).unwrap();
+    let exported = fs::read_to_string(reference.join("merge.ts")).unwrap();
+
+    // an outdated declaration of `Leaf`, importing a type it no longer uses, which was exported
+    // by another process
+    let dir = out_dir("replace");
+    let outdated = exported
+        .replacen("\n\n", "\nimport type { Removed } from \"./Removed\";\n\n", 1)
+        .replace("weight: number", "weight: Removed");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("merge.ts"), &outdated).unwrap();
+    fs::write(
+        dir.join(".ts-rs-manifest.json"),
+        "{\n  \"files\": {\n    \"merge.ts\": []\n  }\n}\n",
+    )
+    .unwrap();
+
+    Leaf::export_all_to(&dir).unwrap();
+
+    assert_eq!(fs::read_to_string(dir.join("merge.ts")).unwrap(), exported);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "module-paths")]
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use ts_rs::TS;
+
+mod server {
+    #[derive(ts_rs::TS)]
+    pub struct Config {
+        pub port: u16,
+        pub client: super::client::Config,
+    }
+}
+
+mod client {
+    #[derive(ts_rs::TS)]
+    pub struct Config {
+        pub url: String,
+    }
+}
+
+#[derive(TS)]
+struct Settings {
+    server: server::Config,
+}
+
+#[derive(TS)]
+#[ts(export_to = "settings/")]
+struct Overridden {
+    settings: Settings,
+}
+
+#[test]
+fn module_paths() {
+    assert_eq!(
+        server::Config::output_path(),
+        Some(Path::new("integration/module_paths/server/Config.ts"))
+    );
+    assert_eq!(
+        client::Config::output_path(),
+        Some(Path::new("integration/module_paths/client/Config.ts"))
+    );
+    assert_eq!(
+        Settings::output_path(),
+        Some(Path::new("integration/module_paths/Settings.ts"))
+    );
+
+    // `export_to` takes precedence
+    assert_eq!(
+        Overridden::output_path(),
+        Some(Path::new("settings/Overridden.ts"))
+    );
+}
+
+#[test]
+fn module_path_imports() {
+    let ext = if cfg!(feature = "import-esm") {
+        ".js"
+    } else {
+        ""
+    };
+
+    // `server::Config` can't import `client::Config` under its own name
+    let server = server::Config::export_to_string().unwrap();
+    assert!(server.contains(&format!(
+        r#"import type {{ Config as Client_Config }} from "../client/Config{ext}";"#
+    )));
+    assert!(server.contains("client: Client_Config"));
+    assert!(!server.contains("import type { Config }"));
+
+    let settings = Settings::export_to_string().unwrap();
+    assert!(settings.contains(&format!(
+        r#"import type {{ Config }} from "./server/Config{ext}";"#
+    )));
+
+    let overridden = Overridden::export_to_string().unwrap();
+    assert!(overridden.contains(&format!(
+        r#"import type {{ Settings }} from "../integration/module_paths/Settings{ext}";"#
+    )));
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use ts_rs::TS;
+
+mod a {
+    #[derive(ts_rs::TS)]
+    #[ts(export_to = "name_collision/a/")]
+    pub struct Error {
+        pub message: String,
+    }
+}
+
+mod b {
+    #[derive(ts_rs::TS)]
+    #[ts(export_to = "name_collision/b/")]
+    pub struct Error {
+        pub code: u32,
+    }
+
+    // with `default-export`, both types named `Status` would be exported into the same file
+    #[cfg(not(feature = "default-export"))]
+    #[derive(ts_rs::TS)]
+    #[ts(export_to = "name_collision/same_file.ts")]
+    pub struct Status {
+        pub code: u32,
+    }
+}
+
+#[derive(TS)]
+#[ts(export_to = "name_collision/")]
+struct Failure {
+    a: a::Error,
+    b: Option<b::Error>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "name_collision/")]
+struct Error {
+    cause: a::Error,
+}
+
+#[cfg(not(feature = "default-export"))]
+#[derive(TS)]
+#[ts(export_to = "name_collision/same_file.ts")]
+struct Status {
+    ok: bool,
+}
+
+#[test]
+#[cfg(not(feature = "import-aliases"))]
+fn colliding_imports() {
+    let error = Failure::export_to_string().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`Error` is declared by both `integration::name_collision::a::Error` and \
+         `integration::name_collision::b::Error`"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "import-aliases"))]
+fn import_colliding_with_declaration() {
+    let error = Error::export_to_string().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`Error` is declared by both `integration::name_collision::Error` and \
+         `integration::name_collision::a::Error`"
+    );
+}
+
+#[test]
+#[cfg(feature = "import-aliases")]
+fn aliased_imports() {
+    let ext = if cfg!(feature = "import-esm") {
+        ".js"
+    } else {
+        ""
+    };
+    let a = format!(r#"import type {{ Error as A_Error }} from "./a/Error{ext}";"#);
+
+    let failure = Failure::export_to_string().unwrap();
+    assert!(failure.contains(&a));
+    assert!(failure.contains(&format!(
+        r#"import type {{ Error as B_Error }} from "./b/Error{ext}";"#
+    )));
+    assert!(failure.contains("export type Failure = { a: A_Error, b: B_Error | null, };"));
+    assert!(!failure.contains("import { ErrorSchema }"));
+
+    // the declaration keeps its name, the import is aliased
+    let error = Error::export_to_string().unwrap();
+    assert!(error.contains(&a));
+    assert!(error.contains(&format!(
+        r#"import {{ ErrorSchema as A_ErrorSchema }} from "./a/Error{ext}";"#
+    )));
+    assert!(error.contains("export type Error = { cause: A_Error, };"));
+    assert!(error.contains(r#""Error": A_ErrorSchema,"#));
+
+    // aliases only apply within the file importing them
+    assert_eq!(a::Error::name(), "Error");
+}
+
+#[test]
+#[cfg(not(feature = "default-export"))]
+fn same_file() {
+    let dir = std::env::temp_dir().join("ts-rs-name-collision");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    Status::export_all_to(&dir).unwrap();
+    let error = b::Status::export_all_to(&dir).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`Status` is declared by both `integration::name_collision::Status` and \
+         `integration::name_collision::b::Status`"
+    );
+}
@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use std::fs;
+
+use ts_rs::TS;
+
+#[derive(TS)]
+#[ts(export_to = "prune/")]
+struct Profile {
+    avatar: Avatar,
+}
+
+#[derive(TS)]
+#[ts(export_to = "prune/assets/")]
+struct Avatar {
+    url: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "prune/shared.ts")]
+struct Kept {
+    id: i32,
+}
+
+#[derive(TS)]
+#[ts(export_to = "prune/shared.ts")]
+struct Removed {
+    id: i32,
+}
+
+const NOTE: &str = "// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.\n";
+
+#[test]
+fn prune_stale_bindings() {
+    let dir = std::env::temp_dir().join("ts-rs-prune");
+    let _ = fs::remove_dir_all(&dir);
+
+    // bindings of a type which no longer exists, and a file which was not generated by ts-rs
+    fs::create_dir_all(dir.join("prune/old")).unwrap();
+    fs::write(
+        dir.join("prune/old/Renamed.ts"),
+        format!("{NOTE}\nexport type Renamed = number;\n"),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("prune/handwritten.ts"),
+        "export type Handwritten = number;\n",
+    )
+    .unwrap();
+
+    Profile::export_all_to(&dir).unwrap();
+    let manifest = fs::read_to_string(dir.join(".ts-rs-manifest.json")).unwrap();
+    assert!(manifest.contains("\"prune/Profile.ts\""));
+    assert!(manifest.contains("\"prune/assets/Avatar.ts\""));
+
+    let pruned = ts_rs::prune(&dir).unwrap();
+    assert_eq!(pruned.len(), 1);
+    assert!(pruned[0].ends_with("prune/old/Renamed.ts"));
+
+    assert!(!dir.join("prune/old").exists());
+    assert!(dir.join("prune/handwritten.ts").exists());
+    assert!(dir.join("prune/Profile.ts").exists());
+    assert!(dir.join("prune/assets/Avatar.ts").exists());
+    assert!(fs::read_to_string(dir.join(".ts-rs-manifest.json"))
+        .unwrap()
+        .is_empty());
+
+    // without a manifest, nothing was exported since the last prune, so nothing is deleted
+    assert!(ts_rs::prune(&dir).unwrap().is_empty());
+    assert!(dir.join("prune/Profile.ts").exists());
+}
+
+#[test]
+fn prune_stale_declarations() {
+    let dir = std::env::temp_dir().join("ts-rs-prune-declarations");
+    let _ = fs::remove_dir_all(&dir);
+
+    // both types were exported before the directory was pruned
+    Kept::export_all_to(&dir).unwrap();
+    Removed::export_all_to(&dir).unwrap();
+    ts_rs::prune(&dir).unwrap();
+
+    // exporting a single type merges it into the existing file
+    Kept::export_all_to(&dir).unwrap();
+    let shared = fs::read_to_string(dir.join("prune/shared.ts")).unwrap();
+    assert!(shared.contains("export type Kept = "));
+    assert!(shared.contains("export type Removed = "));
+
+    // only the declaration of `Removed`, which was not exported since, is removed
+    assert!(ts_rs::prune(&dir).unwrap().is_empty());
+    let shared = fs::read_to_string(dir.join("prune/shared.ts")).unwrap();
+    assert!(shared.starts_with(NOTE));
+    assert!(shared.contains("export type Kept = "));
+    assert!(!shared.contains("Removed"));
+}
@@ -0,0 +1,101 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ts_rs::{RenderOptions, TS};
+
+#[derive(TS)]
+#[ts(export, export_to = "readonly/")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "readonly/", readonly)]
+struct State {
+    id: u32,
+    tags: Vec<String>,
+    scores: HashMap<String, u32>,
+    position: (i32, i32),
+    #[ts(optional)]
+    parent: Option<u32>,
+    #[ts(inline)]
+    origin: Point,
+    last: Point,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "readonly/", readonly = false)]
+struct Mutable {
+    items: Vec<String>,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "readonly/", readonly, tag = "kind")]
+enum Event {
+    Moved { to: Point },
+    Renamed { names: Vec<String> },
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "readonly/", readonly)]
+struct Pair(String, Vec<u32>);
+
+#[test]
+fn readonly_struct() {
+    assert_eq!(
+        State::decl(),
+        "type State = { \
+            readonly id: number, \
+            readonly tags: ReadonlyArray<string>, \
+            readonly scores: { readonly [key in string]?: number }, \
+            readonly position: readonly [number, number], \
+            readonly parent?: number, \
+            readonly origin: { readonly x: number, readonly y: number, }, \
+            readonly last: Point, \
+        };"
+    );
+}
+
+#[test]
+fn readonly_is_limited_to_type() {
+    let _ = State::decl();
+
+    assert_eq!(Point::decl(), "type Point = { x: number, y: number, };");
+    assert_eq!(Vec::<String>::inline(), "Array<string>");
+    assert_eq!(Mutable::decl(), "type Mutable = { items: Array<string>, };");
+}
+
+#[test]
+fn readonly_option() {
+    // set by `readonly = true` in the configuration of the crate declaring the type
+    let options = RenderOptions::current().readonly(true);
+
+    assert_eq!(
+        options.scope(Point::decl),
+        "type Point = { readonly x: number, readonly y: number, };"
+    );
+    assert_eq!(
+        options.scope(Mutable::decl),
+        "type Mutable = { items: Array<string>, };"
+    );
+}
+
+#[test]
+fn readonly_enum() {
+    assert_eq!(
+        Event::decl(),
+        "type Event = \
+            { readonly \"kind\": \"Moved\", readonly to: Point, } | \
+            { readonly \"kind\": \"Renamed\", readonly names: ReadonlyArray<string>, };"
+    );
+}
+
+#[test]
+fn readonly_tuple_struct() {
+    assert_eq!(
+        Pair::decl(),
+        "type Pair = readonly [string, ReadonlyArray<number>];"
+    );
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "registry")]
+#![allow(dead_code)]
+
+use ts_rs::TS;
+
+#[derive(TS)]
+#[ts(export, export_to = "registry/")]
+struct Account {
+    owner: Owner,
+    balance: u64,
+}
+
+#[derive(TS)]
+#[ts(export_to = "registry/")]
+struct Owner {
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "registry/")]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "registry/")]
+struct Unregistered {
+    id: u32,
+}
+
+#[test]
+fn registered_types() {
+    let registered = ts_rs::registered();
+    let find = |path: &str| registered.iter().find(|r| r.rust_path() == path);
+
+    let account = find("integration::registry::Account").unwrap();
+    assert_eq!(account.ident(), "Account");
+    assert_eq!(
+        account.output_path(),
+        Some(std::path::Path::new("registry/Account.ts"))
+    );
+    assert_eq!(find("integration::registry::Page").unwrap().ident(), "Page");
+
+    // with `default-export`, every type is registered
+    if !cfg!(feature = "default-export") {
+        assert!(find("integration::registry::Owner").is_none());
+        assert!(find("integration::registry::Unregistered").is_none());
+    }
+
+    let paths = registered.iter().map(|r| r.rust_path()).collect::<Vec<_>>();
+    assert!(paths.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn export_registrations() {
+    let dir = std::env::temp_dir().join("ts-rs-registry");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // other modules of this test binary register types as well, so we only export our own
+    ts_rs::registered()
+        .into_iter()
+        .filter(|r| r.rust_path().starts_with("integration::registry::"))
+        .try_for_each(|r| r.export_all_to(&dir))
+        .unwrap();
+
+    assert!(dir.join("registry/Account.ts").is_file());
+    assert!(dir.join("registry/Owner.ts").is_file());
+    assert!(dir.join("registry/Page.ts").is_file());
+    assert_eq!(
+        dir.join("registry/Unregistered.ts").exists(),
+        cfg!(feature = "default-export")
+    );
+}
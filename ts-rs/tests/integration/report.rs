@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ts_rs::{Directory, ExportError, ExportSink, FileStatus, TS};
+
+#[derive(TS)]
+#[ts(export_to = "report/")]
+struct Invoice {
+    id: u32,
+    customer: Customer,
+    items: Vec<Item>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "report/")]
+struct Customer {
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "report/items.ts")]
+struct Item {
+    price: f64,
+}
+
+#[test]
+fn in_memory() {
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    let report = Invoice::export_all_with_report(&mut files).unwrap();
+
+    let invoice = &report.files[Path::new("report/Invoice.ts")];
+    assert_eq!(invoice.status, FileStatus::Created);
+    assert_eq!(invoice.types, ["Invoice"]);
+    assert_eq!(invoice.schemas, ["InvoiceSchema"]);
+    assert_eq!(report.files[Path::new("report/items.ts")].types, ["Item"]);
+    assert_eq!(report.changed().count(), report.files.len());
+
+    // primitives can't be exported
+    assert!(report.skipped.contains(std::any::type_name::<u32>()));
+    assert!(report.skipped.contains(std::any::type_name::<String>()));
+    assert!(!report.skipped.contains(std::any::type_name::<Item>()));
+
+    // exporting again doesn't change anything, unless a file was modified
+    files
+        .get_mut(Path::new("report/Customer.ts"))
+        .unwrap()
+        .push_str("// modified\n");
+    let report = Invoice::export_all_with_report(&mut files).unwrap();
+    let changed = report.changed().collect::<Vec<_>>();
+    assert_eq!(changed, [Path::new("report/Customer.ts")]);
+    assert_eq!(
+        report.files[Path::new("report/Customer.ts")].status,
+        FileStatus::Updated
+    );
+    assert_eq!(
+        report.files[Path::new("report/Invoice.ts")].status,
+        FileStatus::Unchanged
+    );
+}
+
+/// Keeps the files in memory, counting how often they are written
+#[derive(Default)]
+struct Counting {
+    files: BTreeMap<PathBuf, String>,
+    writes: usize,
+}
+
+impl ExportSink for Counting {
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        self.files.read(path)
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        self.writes += 1;
+        self.files.write(path, contents)
+    }
+}
+
+#[test]
+fn unchanged_files_are_not_written() {
+    let mut sink = Counting::default();
+    Invoice::export_all_with_report(&mut sink).unwrap();
+    assert!(sink.writes > 0);
+
+    sink.writes = 0;
+    let report = Invoice::export_all_with_report(&mut sink).unwrap();
+    assert_eq!(report.changed().count(), 0);
+    assert_eq!(sink.writes, 0);
+}
+
+#[test]
+fn directory() {
+    let dir = std::env::temp_dir().join("ts-rs-report");
+    let _ = fs::remove_dir_all(&dir);
+
+    let report = Invoice::export_all_with_report(&mut Directory::new(&dir)).unwrap();
+    assert!(report
+        .files
+        .values()
+        .all(|file| file.status == FileStatus::Created));
+
+    // files written by a previous process are compared as well
+    ts_rs::prune(&dir).unwrap();
+    let report = Customer::export_all_with_report(&mut Directory::new(&dir)).unwrap();
+    assert_eq!(
+        report.files[Path::new("report/Customer.ts")].status,
+        FileStatus::Unchanged
+    );
+}
+
+#[test]
+fn json() {
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    let json = Customer::export_all_with_report(&mut files)
+        .unwrap()
+        .to_json();
+
+    assert!(json.starts_with("{\n  \"files\": [\n    {\n      \"path\": \"report/Customer.ts\",\n"));
+    assert!(json.contains("\"status\": \"created\",\n      \"types\": [\"Customer\"],\n      \"schemas\": [\"CustomerSchema\"],\n"));
+    assert!(json.contains(&format!(
+        "\"skipped\": [\"{}\"],",
+        std::any::type_name::<String>()
+    )));
+    assert!(json.ends_with("}\n"));
+}
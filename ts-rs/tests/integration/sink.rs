@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ts_rs::{Directory, ExportError, ExportSink, TS};
+
+#[derive(TS)]
+#[ts(export_to = "sink/")]
+struct Order {
+    id: u32,
+    customer: Customer,
+    lines: Vec<Line>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "sink/")]
+struct Customer {
+    name: String,
+}
+
+#[derive(TS)]
+#[ts(export_to = "sink/lines.ts")]
+struct Line {
+    quantity: u32,
+    discount: Option<Discount>,
+}
+
+#[derive(TS)]
+#[ts(export_to = "sink/lines.ts")]
+struct Discount {
+    percent: f32,
+}
+
+#[test]
+fn in_memory() {
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    Order::export_all_into(&mut files).unwrap();
+
+    let paths = files.keys().map(PathBuf::as_path).collect::<Vec<_>>();
+    assert!(paths.contains(&Path::new("sink/Order.ts")));
+    assert!(paths.contains(&Path::new("sink/Customer.ts")));
+    assert!(paths.contains(&Path::new("sink/lines.ts")));
+
+    // types exported into the same file are merged
+    let lines = &files[Path::new("sink/lines.ts")];
+    assert!(lines.contains("export type Line = "));
+    assert!(lines.contains("export type Discount = "));
+    assert!(files[Path::new("sink/Order.ts")].contains(r#"from "./lines"#));
+}
+
+#[test]
+fn in_memory_matches_directory() {
+    let dir = std::env::temp_dir().join("ts-rs-sink");
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut files = BTreeMap::<PathBuf, String>::new();
+    Order::export_all_into(&mut files).unwrap();
+    Order::export_all_into(&mut Directory::new(&dir)).unwrap();
+
+    for (path, contents) in &files {
+        assert_eq!(&fs::read_to_string(dir.join(path)).unwrap(), contents);
+    }
+}
+
+/// A sink which only accepts files within a single directory
+struct Flat(BTreeMap<PathBuf, String>);
+
+impl ExportSink for Flat {
+    fn read(&mut self, path: &Path) -> Result<Option<String>, ExportError> {
+        Ok(self.0.get(path).cloned())
+    }
+
+    fn write(&mut self, path: &Path, contents: String) -> Result<(), ExportError> {
+        if path.parent() != Some(Path::new("sink")) {
+            return Err(ExportError::CannotBeExported("nested"));
+        }
+        self.0.insert(path.to_owned(), contents);
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_sink() {
+    let mut flat = Flat(BTreeMap::new());
+    Order::export_all_into(&mut flat).unwrap();
+    assert!(flat.0.contains_key(Path::new("sink/lines.ts")));
+
+    let sink: &mut dyn ExportSink = &mut flat;
+    Customer::export_all_into(sink).unwrap();
+}
+
+#[test]
+#[cfg(feature = "tar-archive")]
+fn tar_archive() {
+    let mut archive = ts_rs::TarArchive::new(Vec::new());
+    Order::export_all_into(&mut archive).unwrap();
+    let bytes = archive.finish().unwrap();
+
+    let contents = String::from_utf8_lossy(&bytes);
+    assert!(contents.contains("sink/Order.ts"));
+    assert!(contents.contains("export type Discount = "));
+
+    // archives are reproducible
+    let mut archive = ts_rs::TarArchive::new(Vec::new());
+    Order::export_all_into(&mut archive).unwrap();
+    assert_eq!(archive.finish().unwrap(), bytes);
+}
+
+#[test]
+#[cfg(feature = "zip-archive")]
+fn zip_archive() {
+    let mut archive = ts_rs::ZipArchive::new(std::io::Cursor::new(Vec::new()));
+    Order::export_all_into(&mut archive).unwrap();
+    let bytes = archive.finish().unwrap().into_inner();
+
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+    let contents = String::from_utf8_lossy(&bytes);
+    for path in ["sink/Order.ts", "sink/Customer.ts", "sink/lines.ts"] {
+        assert!(contents.contains(path));
+    }
+}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use ts_rs::{RenderOptions, TS};
+
+#[derive(TS)]
+#[ts(export, export_to = "typed_arrays/")]
+struct Image {
+    #[ts(typed_array)]
+    pixels: Vec<u8>,
+    #[ts(typed_array)]
+    depth: Option<Vec<f32>>,
+    #[ts(typed_array)]
+    samples: [i16; 4],
+    #[ts(typed_array)]
+    timestamps: Vec<u64>,
+    labels: Vec<u8>,
+}
+
+#[derive(TS)]
+#[ts(export, export_to = "typed_arrays/")]
+struct Frame(#[ts(typed_array)] Vec<u8>);
+
+#[test]
+fn typed_array_fields() {
+    assert_eq!(
+        Image::decl(),
+        "type Image = { \
+            pixels: Uint8Array, \
+            depth: Float32Array | null, \
+            samples: Int16Array, \
+            timestamps: BigUint64Array, \
+            labels: Array<number>, \
+        };"
+    );
+    assert_eq!(Frame::decl(), "type Frame = Uint8Array;");
+
+    let options = RenderOptions::current().typed_arrays(true);
+    assert!(options.scope(Image::decl).contains("labels: Uint8Array, "));
+}
+
+#[test]
+fn scope_is_limited_to_field() {
+    let _ = Image::decl();
+
+    assert_eq!(Vec::<u8>::name(), "Array<number>");
+    assert_eq!(<[u8; 2]>::name(), "[number, number]");
+}
+
+#[test]
+fn typed_array_schema() {
+    let schema = Image::schema(false);
+
+    assert!(schema.contains("\"type\": \"Vec<u8>\",\n      \"typedArray\": \"Uint8Array\""));
+    assert!(schema.contains(r#""typedArray": "Float32Array""#));
+    assert!(schema.contains(r#""typedArray": "Int16Array""#));
+    assert!(schema.contains(r#""typedArray": "BigUint64Array""#));
+    assert_eq!(schema.matches(r#""typedArray""#).count(), 4);
+
+    // fields without `#[ts(typed_array)]` are marked if typed arrays are enabled
+    let options = RenderOptions::current().typed_arrays(true);
+    let schema = options.scope(|| Image::schema(false));
+    assert_eq!(schema.matches(r#""typedArray""#).count(), 5);
+    assert!(!schema.contains("%%"));
+}
+
+#[test]
+fn fixed_length_schema() {
+    // arrays are encoded without a length prefix, so their length is part of the schema
+    let options = RenderOptions::current().typed_arrays(true);
+    assert_eq!(
+        options.scope(|| <[u8; 4]>::schema(false)),
+        r#"{ "type" : "array", "items" : { "type" : "number" }, "length" : 4, "typedArray" : "Uint8Array" }"#
+    );
+    assert!(!options
+        .scope(|| Vec::<u8>::schema(false))
+        .contains("length"));
+}